@@ -7,11 +7,19 @@
 //! Cargo re-run the build script whenever `memory.x` is changed,
 //! updating `memory.x` ensures a rebuild of the application with the
 //! new memory settings.
+//!
+//! It also generates the buffer sizes from `capacities.conf` (see there), and
+//! fails the build if they do not fit in the RAM that `memory.x` describes.
+//!
+//! With the `preparse-04` or `preparse-11` features, it also parses that day's
+//! input into Rust source for a `static`, so the device skips the parsing.
 
+use std::collections::BTreeMap;
 use std::env;
-use std::fs::File;
+use std::fmt::Write as _;
+use std::fs::{self, File};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 fn main() {
     // Put `memory.x` in our output directory and ensure it's
@@ -28,4 +36,276 @@ fn main() {
     // here, we ensure the build script is only re-run when
     // `memory.x` is changed.
     println!("cargo:rerun-if-changed=memory.x");
+
+    let caps = capacities(out);
+
+    if env::var_os("CARGO_FEATURE_PREPARSE_04").is_some() {
+        preparse(out, "04", day04);
+    }
+    if env::var_os("CARGO_FEATURE_PREPARSE_11").is_some() {
+        preparse(out, "11", |input| day11(input, &caps));
+    }
+}
+
+/// The settings in `capacities.conf`. Every one must be set.
+const CAPACITIES: [&str; 5] = [
+    "DAY01_TOP_K",
+    "DAY01_MAX_ELVES",
+    "DAY11_NUM_MONKEYS",
+    "DAY11_MAX_ITEMS",
+    "RAM_RESERVE",
+];
+
+type Capacities = BTreeMap<&'static str, usize>;
+
+/// Reads the capacities (from `AOC22_CAPACITIES`, or else `capacities.conf`, with any
+/// `AOC22_<NAME>` overrides), checks them against the RAM and writes
+/// `$OUT_DIR/capacities.rs`.
+fn capacities(out: &Path) -> Capacities {
+    println!("cargo:rerun-if-env-changed=AOC22_CAPACITIES");
+    let path = env::var("AOC22_CAPACITIES").unwrap_or_else(|_| "capacities.conf".to_string());
+    println!("cargo:rerun-if-changed={}", path);
+    let config = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+
+    let mut caps = Capacities::new();
+    for (n, line) in config.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let (name, value) = line
+            .split_once('=')
+            .unwrap_or_else(|| panic!("{}:{}: expected NAME = value", path, n + 1));
+        let name = CAPACITIES
+            .into_iter()
+            .find(|&known| known == name.trim())
+            .unwrap_or_else(|| panic!("{}:{}: unknown setting {:?}", path, n + 1, name.trim()));
+        let value = size(value).unwrap_or_else(|e| panic!("{}:{}: {}", path, n + 1, e));
+        caps.insert(name, value);
+    }
+    for name in CAPACITIES {
+        let var = format!("AOC22_{}", name);
+        println!("cargo:rerun-if-env-changed={}", var);
+        if let Ok(value) = env::var(&var) {
+            let value = size(&value).unwrap_or_else(|e| panic!("{}: {}", var, e));
+            caps.insert(name, value);
+        }
+        match caps.get(name) {
+            None => panic!("{}: {} is not set", path, name),
+            Some(0) if name != "RAM_RESERVE" => panic!("{} must not be 0", name),
+            _ => {}
+        }
+    }
+
+    check_ram(&caps);
+
+    let mut source = format!("// Generated by `build.rs` from `{}`.\n", path);
+    for (name, value) in caps.iter().filter(|(name, _)| **name != "RAM_RESERVE") {
+        writeln!(source, "pub const {}: usize = {};", name, value).unwrap();
+    }
+    fs::write(out.join("capacities.rs"), source).unwrap();
+    caps
+}
+
+/// Fails the build if a day's buffers, plus `RAM_RESERVE`, need more RAM than the
+/// board has. Only one day runs per binary, so each day is checked on its own.
+///
+/// These are rough counts of the big arrays on the stack, not exact frame sizes;
+/// `cargo test --test target` and the stack figures in the reports have the last word.
+fn check_ram(caps: &Capacities) {
+    let ram = memory_ram().expect("memory.x: no RAM LENGTH");
+    // A `Monkey` is its items plus 64 bytes: the queue's indices, the inspection
+    // count, the operation, the modulus and the two targets.
+    let monkey = 8 * caps["DAY11_MAX_ITEMS"] + 64;
+    let days = [
+        // The top `K` totals with their elves, and every elf's total for `stats`.
+        (
+            "day01",
+            16 * caps["DAY01_TOP_K"] + 4 * caps["DAY01_MAX_ELVES"],
+        ),
+        // The parsed monkeys, and the copy each part plays with.
+        ("day11", 2 * caps["DAY11_NUM_MONKEYS"] * monkey),
+    ];
+    for (day, bytes) in days {
+        let needed = bytes + caps["RAM_RESERVE"];
+        if needed > ram {
+            panic!(
+                "{} needs about {} bytes of RAM ({} for its buffers, {} reserved), but memory.x only has {}",
+                day, needed, bytes, caps["RAM_RESERVE"], ram
+            );
+        }
+    }
+}
+
+/// The `LENGTH` of the `RAM` region in `memory.x`.
+fn memory_ram() -> Option<usize> {
+    let line = include_str!("memory.x")
+        .lines()
+        .find(|line| line.trim_start().starts_with("RAM") && line.contains("LENGTH"))?;
+    let length = line.split("LENGTH").nth(1)?.trim_start_matches([' ', '=']);
+    let length = length.split([',', ' ']).next()?;
+    size(length).ok()
+}
+
+/// A size such as `8192`, `0x2000` or `8K`.
+fn size(value: &str) -> Result<usize, String> {
+    let value = value.trim();
+    let (digits, scale) = match value.as_bytes().last() {
+        Some(b'K') => (&value[..value.len() - 1], 1024),
+        Some(b'M') => (&value[..value.len() - 1], 1024 * 1024),
+        _ => (value, 1),
+    };
+    let number = match digits.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => digits.parse(),
+    };
+    number
+        .map(|n| n * scale)
+        .map_err(|e| format!("bad size {:?}: {}", value, e))
+}
+
+/// Turns `input/NN.txt` into `$OUT_DIR/dayNN_input.rs`, an expression for the day
+/// module to `include!`.
+fn preparse(out: &Path, day: &str, parse: impl Fn(&str) -> Result<String, String>) {
+    let path = format!("input/{}.txt", day);
+    println!("cargo:rerun-if-changed={}", path);
+    let input = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    let source = parse(&input).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    fs::write(out.join(format!("day{}_input.rs", day)), source).unwrap();
+}
+
+/// `2-4,6-8` lines become `[(Assignment { low: 2, high: 4 }, ...), ...]`.
+fn day04(input: &str) -> Result<String, String> {
+    let assignment = |s: &str| -> Result<String, String> {
+        let (low, high) = s.split_once('-').ok_or(format!("bad assignment {:?}", s))?;
+        let number = |n: &str| n.parse::<u32>().map_err(|e| format!("{:?}: {}", n, e));
+        Ok(format!(
+            "Assignment {{ low: {}, high: {} }}",
+            number(low)?,
+            number(high)?
+        ))
+    };
+    let mut source = String::from("[\n");
+    for line in input.lines().filter(|line| !line.trim().is_empty()) {
+        let (first, second) = line
+            .trim()
+            .split_once(',')
+            .ok_or(format!("bad line {:?}", line))?;
+        writeln!(
+            source,
+            "    ({}, {}),",
+            assignment(first)?,
+            assignment(second)?
+        )
+        .unwrap();
+    }
+    source.push(']');
+    Ok(source)
+}
+
+/// Each monkey becomes a `(Monkey { ... }, &[items])` pair in an array.
+fn day11(input: &str, caps: &Capacities) -> Result<String, String> {
+    let mut source = String::from("[\n");
+    let input = input.replace("\r\n", "\n");
+    let monkeys: Vec<_> = input
+        .split("\n\n")
+        .filter(|m| !m.trim().is_empty())
+        .collect();
+    if monkeys.len() > caps["DAY11_NUM_MONKEYS"] {
+        return Err(format!(
+            "{} monkeys, but DAY11_NUM_MONKEYS is {}",
+            monkeys.len(),
+            caps["DAY11_NUM_MONKEYS"]
+        ));
+    }
+    let count = monkeys.len() as u64;
+    for (index, monkey) in monkeys.into_iter().enumerate() {
+        let mut lines = monkey.lines().map(str::trim);
+        let mut field = |prefix: &str| -> Result<&str, String> {
+            let line = lines.next().unwrap_or("");
+            line.strip_prefix(prefix)
+                .ok_or(format!("expected {:?}, got {:?}", prefix, line))
+        };
+        let header = field("Monkey ")?;
+        let header = header
+            .strip_suffix(':')
+            .ok_or(format!("bad monkey header {:?}", header))?;
+        if number(header)? != index as u64 {
+            return Err(format!(
+                "monkeys out of order: block {} is monkey {}",
+                index, header
+            ));
+        }
+        let items = field("Starting items:")?.trim();
+        let op: Vec<_> = field("Operation: new =")?.split_whitespace().collect();
+        let term = |t: &str| -> Result<String, String> {
+            Ok(match t {
+                "old" => "Term::Old".to_string(),
+                n => format!("Term::Literal({})", number(n)?),
+            })
+        };
+        let op = match op[..] {
+            [left, operator, right] => format!(
+                "MonkeyOp {{ left: {}, operator: Operator::{}, right: {} }}",
+                term(left)?,
+                match operator {
+                    "+" => "Add",
+                    "-" => "Sub",
+                    "*" => "Mul",
+                    "/" if right != "old" && number(right)? == 0 => {
+                        return Err("division by 0".to_string());
+                    }
+                    "/" => "Div",
+                    _ => return Err(format!("bad operator {:?}", operator)),
+                },
+                term(right)?
+            ),
+            _ => return Err(format!("bad operation in {:?}", monkey)),
+        };
+        let modulus = number(field("Test: divisible by ")?)?;
+        if modulus == 0 {
+            return Err("divisible by 0".to_string());
+        }
+        let if_true = number(field("If true: throw to monkey ")?)?;
+        let if_false = number(field("If false: throw to monkey ")?)?;
+        for target in [if_true, if_false] {
+            if target >= count {
+                return Err(format!(
+                    "monkey {} throws to missing monkey {}",
+                    index, target
+                ));
+            }
+            if target == index as u64 {
+                return Err(format!("monkey {} throws to itself", index));
+            }
+        }
+        let items = items
+            .split(',')
+            .filter(|n| !n.trim().is_empty())
+            .map(|n| number(n.trim()).map(|n| n.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if items.len() > caps["DAY11_MAX_ITEMS"] {
+            return Err(format!(
+                "{} starting items, but DAY11_MAX_ITEMS is {}",
+                items.len(),
+                caps["DAY11_MAX_ITEMS"]
+            ));
+        }
+        writeln!(
+            source,
+            "    (Monkey {{ num_inspections: 0, items: Deque::new(), op: {}, modulus: {}, if_true: {}, if_false: {} }}, &[{}]),",
+            op,
+            modulus,
+            if_true,
+            if_false,
+            items.join(", ")
+        )
+        .unwrap();
+    }
+    source.push(']');
+    Ok(source)
+}
+
+fn number(n: &str) -> Result<u64, String> {
+    n.trim().parse().map_err(|e| format!("{:?}: {}", n, e))
 }
@@ -0,0 +1,26 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+#![no_main]
+
+use aoc22_nostd::day02;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = day02::parse_part1_round(data);
+    let _ = day02::parse_part2_round(data);
+    let rpsls = day02::ROCK_PAPER_SCISSORS_LIZARD_SPOCK;
+    let _ = rpsls.parse_part1_round(data);
+    let _ = rpsls.parse_part2_round(data);
+});
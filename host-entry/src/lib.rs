@@ -0,0 +1,57 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! A stand-in for `cortex_m_rt::entry` when the days run natively on the host.
+
+#![warn(clippy::all)]
+
+use proc_macro::{TokenStream, TokenTree};
+
+fn error(msg: &str) -> TokenStream {
+    format!("compile_error!({:?});", msg).parse().unwrap()
+}
+
+/// Exports a C `main` that calls the annotated `fn name() -> !`, so a `#![no_main]`
+/// binary starts there just like it would from the Cortex-M reset handler.
+#[proc_macro_attribute]
+pub fn entry(args: TokenStream, item: TokenStream) -> TokenStream {
+    if !args.is_empty() {
+        return error("#[entry] takes no arguments");
+    }
+    let mut tokens = item.clone().into_iter();
+    let name = loop {
+        match tokens.next() {
+            Some(TokenTree::Ident(i)) if i.to_string() == "fn" => match tokens.next() {
+                Some(TokenTree::Ident(name)) => break name,
+                _ => return error("expected a function name"),
+            },
+            Some(_) => {}
+            None => return error("#[entry] must be on a function"),
+        }
+    };
+    let trampoline: TokenStream = format!(
+        r#"
+        #[export_name = "main"]
+        extern "C" fn __aoc22_host_entry(_argc: i32, _argv: *const *const u8) -> i32 {{
+            {}()
+        }}
+        "#,
+        name
+    )
+    .parse()
+    .unwrap();
+    let mut out = item;
+    out.extend(trampoline);
+    out
+}
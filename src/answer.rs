@@ -0,0 +1,92 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use core::fmt::{self, Write};
+use heapless::String;
+
+/// Longest text answer we can hold (stack tops, CRT letters, SNAFU numbers).
+pub const ANSWER_TEXT_LEN: usize = 32;
+
+/// The answer to one part of a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Unsigned(u64),
+    /// For products of two `u64`s, such as day 11 over very many rounds.
+    Unsigned128(u128),
+    Signed(i64),
+    Text(String<ANSWER_TEXT_LEN>),
+}
+
+impl Answer {
+    /// Makes a text answer, or `None` if `s` is longer than `ANSWER_TEXT_LEN`.
+    pub fn text(s: &str) -> Option<Answer> {
+        let mut text = String::new();
+        text.push_str(s).ok()?;
+        Some(Answer::Text(text))
+    }
+
+    /// Checks the answer against its expected rendering, e.g. "24000" or "CMZ".
+    pub fn matches(&self, expected: &str) -> bool {
+        // Wide enough for any u128, and for the longest text answer.
+        let mut rendered: String<40> = String::new();
+        write!(rendered, "{}", self).is_ok() && rendered == expected.trim()
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(v) => write!(f, "{}", v),
+            Answer::Unsigned128(v) => write!(f, "{}", v),
+            Answer::Signed(v) => write!(f, "{}", v),
+            Answer::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(v: u32) -> Self {
+        Answer::Unsigned(v.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(v: u64) -> Self {
+        Answer::Unsigned(v)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(v: u128) -> Self {
+        Answer::Unsigned128(v)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Self {
+        Answer::Unsigned(v as u64)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(v: i32) -> Self {
+        Answer::Signed(v.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(v: i64) -> Self {
+        Answer::Signed(v)
+    }
+}
@@ -0,0 +1,48 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! The command line, from semihosting (or the process, natively).
+//!
+//! Under QEMU it is the kernel's path followed by what `-append` gives, e.g.
+//! `cargo run --release --bin day11 -- -append 1000000000000`.
+
+/// Longest command line, including the kernel's path.
+#[cfg(not(feature = "host"))]
+const CMDLINE_LEN: usize = 256;
+
+/// Calls `f` with word `n` of the command line, where word 0 is the program.
+#[cfg(not(feature = "host"))]
+pub fn with_arg<R>(n: usize, f: impl FnOnce(Option<&str>) -> R) -> R {
+    use cortex_m_semihosting::syscall;
+
+    let mut buf = [0u8; CMDLINE_LEN];
+    // SAFETY: `buf` is valid for writes of `buf.len()` bytes during the call.
+    // SYS_GET_CMDLINE fills it with a NUL-terminated string, and returns 0 if it fit.
+    let status = unsafe { syscall!(GET_CMDLINE, buf.as_mut_ptr(), buf.len()) };
+    let len = match status {
+        0 => buf.iter().position(|&b| b == 0).unwrap_or(0),
+        _ => 0,
+    };
+    let line = buf
+        .get(..len)
+        .and_then(|line| core::str::from_utf8(line).ok());
+    f(line.and_then(|line| line.split_ascii_whitespace().nth(n)))
+}
+
+/// Calls `f` with word `n` of the command line, where word 0 is the program.
+#[cfg(feature = "host")]
+pub fn with_arg<R>(n: usize, f: impl FnOnce(Option<&str>) -> R) -> R {
+    let arg = std::env::args().nth(n);
+    f(arg.as_deref())
+}
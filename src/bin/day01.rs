@@ -16,81 +16,21 @@
 #![no_std]
 #![no_main]
 
-use cortex_m_rt::entry;
-use cortex_m_semihosting::{debug, hprintln};
-use heapless::binary_heap::{BinaryHeap, Min};
-use nom::{
-    character::complete::{line_ending, u32},
-    combinator::opt,
-    multi::fold_many1,
-    sequence::terminated,
-    IResult,
-};
-use panic_semihosting as _;
+#[cfg(not(feature = "const-eval"))]
+use aoc22_nostd::run;
+#[cfg(feature = "const-eval")]
+use aoc22_nostd::run_const;
+use aoc22_nostd::{day01, day01::Day01, entry, expected};
 
-type Elf = u32;
-
-fn parse_elf(i: &str) -> IResult<&str, Elf> {
-    let (i, _) = opt(line_ending)(i)?;
-    let (i, elf) = fold_many1(
-        terminated(u32, line_ending),
-        || 0,
-        |mut sum, val| {
-            sum += val;
-            sum
-        },
-    )(i)?;
-    Ok((i, elf))
-}
-
-fn part1(i: &str) -> Elf {
-    let (_, most) = fold_many1(
-        parse_elf,
-        || 0,
-        |most, val| {
-            if most > val {
-                most
-            } else {
-                val
-            }
-        },
-    )(i)
-    .unwrap();
-    most
-}
-
-fn part2(i: &str) -> Elf {
-    // To get the K largest values, use a min-heap of K+1 and keep pruning it to K.
-    const K: usize = 3;
-    let mut heap: BinaryHeap<Elf, Min, { K + 1 }> = BinaryHeap::new();
-    let (_, _) = fold_many1(
-        parse_elf,
-        || 0,
-        |_, val| {
-            if val > *heap.peek().unwrap_or(&0) {
-                heap.push(val).unwrap();
-            }
-            if heap.len() > K {
-                heap.pop().unwrap();
-            }
-            val
-        },
-    )(i)
-    .unwrap();
-    heap.into_iter().sum()
-}
+const INPUT: &str = include_str!("../../input/01.txt");
 
 #[entry]
 fn main() -> ! {
-    let input = include_str!("../../input/01.txt");
-
-    let p1 = part1(input);
-    hprintln!("Part 1: {:?}", p1).unwrap();
-
-    let p2 = part2(input);
-    hprintln!("Part 2: {:?}", p2).unwrap();
-
-    // Exit QEMU.
-    debug::exit(debug::EXIT_SUCCESS);
-    unreachable!()
+    #[cfg(feature = "const-eval")]
+    run_const!(Day01, day01::answers(INPUT.as_bytes()), expected!("01"));
+    #[cfg(not(feature = "const-eval"))]
+    {
+        day01::report_stats(INPUT);
+        run::<Day01>(INPUT, expected!("01"))
+    }
 }
@@ -16,97 +16,22 @@
 #![no_std]
 #![no_main]
 
-use cortex_m_rt::entry;
-use cortex_m_semihosting::{debug, hprintln};
-use nom::{
-    character::complete::{char, line_ending, u32},
-    combinator::opt,
-    multi::fold_many1,
-    sequence::separated_pair,
-    IResult,
-};
-use panic_semihosting as _;
-
-struct Assignment {
-    low: u32,
-    high: u32,
-}
-
-impl Assignment {
-    fn contained_within(&self, other: &Assignment) -> bool {
-        self.low <= other.low && self.high >= other.high
-    }
-
-    fn overlapping(&self, other: &Assignment) -> bool {
-        (self.low >= other.low && self.low <= other.high)
-            || (self.high >= other.low && self.high <= other.high)
-            || (self.low < other.low && self.high > other.high)
-    }
-}
-
-fn parse_assignment_pair(i: &str) -> IResult<&str, (Assignment, Assignment)> {
-    let (i, ((first_low, first_high), (second_low, second_high))) = separated_pair(
-        separated_pair(u32, char('-'), u32),
-        char(','),
-        separated_pair(u32, char('-'), u32),
-    )(i)?;
-    let (i, _) = opt(line_ending)(i)?;
-    Ok((
-        i,
-        (
-            Assignment {
-                low: first_low,
-                high: first_high,
-            },
-            Assignment {
-                low: second_low,
-                high: second_high,
-            },
-        ),
-    ))
-}
-
-fn part1(i: &str) -> u32 {
-    let (_, total) = fold_many1(
-        parse_assignment_pair,
-        || 0,
-        |mut result, (first, second)| {
-            if first.contained_within(&second) || second.contained_within(&first) {
-                result += 1;
-            }
-            result
-        },
-    )(i)
-    .unwrap();
-    total
-}
-
-fn part2(i: &str) -> u32 {
-    let (_, total) = fold_many1(
-        parse_assignment_pair,
-        || 0,
-        |mut result, (first, second)| {
-            if first.overlapping(&second) {
-                result += 1;
-            }
-            result
-        },
-    )(i)
-    .unwrap();
-    total
-}
+#[cfg(not(feature = "preparse-04"))]
+use aoc22_nostd::day04::Day04;
+#[cfg(feature = "preparse-04")]
+use aoc22_nostd::day04::Day04Preparsed as Day04;
+#[cfg(not(feature = "const-eval"))]
+use aoc22_nostd::run;
+#[cfg(feature = "const-eval")]
+use aoc22_nostd::{day04, run_const};
+use aoc22_nostd::{entry, expected};
+
+const INPUT: &str = include_str!("../../input/04.txt");
 
 #[entry]
 fn main() -> ! {
-    let input = include_str!("../../input/04.txt");
-
-    let p1 = part1(input);
-    hprintln!("Part 1: {:?}", p1).unwrap();
-
-    let p2 = part2(input);
-    hprintln!("Part 2: {:?}", p2).unwrap();
-
-    // Exit QEMU.
-    debug::exit(debug::EXIT_SUCCESS);
-    unreachable!()
+    #[cfg(feature = "const-eval")]
+    run_const!(Day04, day04::answers(INPUT.as_bytes()), expected!("04"));
+    #[cfg(not(feature = "const-eval"))]
+    run::<Day04>(INPUT, expected!("04"))
 }
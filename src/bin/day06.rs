@@ -16,67 +16,22 @@
 #![no_std]
 #![no_main]
 
-use cortex_m_rt::entry;
-use cortex_m_semihosting::{debug, hprintln};
-use panic_semihosting as _;
-
-fn parse(input: &[u8], output: &mut [u8]) {
-    for (i, ch) in input.iter().enumerate() {
-        output[i] = *ch;
-    }
-}
-
-fn part1(puzzle: &[u8]) -> i32 {
-    let mut marker: [u8; 4] = puzzle[0..4].try_into().unwrap();
-    for (idx, ch) in puzzle.iter().enumerate() {
-        marker[idx % 4] = *ch;
-        let mut dup = false;
-        for i in 0..4 {
-            for j in i + 1..4 {
-                if marker[i] == marker[j] {
-                    dup = true;
-                }
-            }
-        }
-        if !dup {
-            return idx as i32 + 1;
-        }
-    }
-    0
-}
-
-fn part2(puzzle: &[u8]) -> i32 {
-    let mut marker: [u8; 14] = puzzle[0..14].try_into().unwrap();
-    for (idx, ch) in puzzle.iter().enumerate() {
-        marker[idx % 14] = *ch;
-        let mut dup = false;
-        for i in 0..14 {
-            for j in i + 1..14 {
-                if marker[i] == marker[j] {
-                    dup = true;
-                }
-            }
-        }
-        if !dup {
-            return idx as i32 + 1;
-        }
-    }
-    0
-}
+#[cfg(not(feature = "console-06"))]
+use aoc22_nostd::day06::Day06;
+#[cfg(feature = "console-06")]
+use aoc22_nostd::day06::Day06Console as Day06;
+#[cfg(not(feature = "const-eval"))]
+use aoc22_nostd::run;
+#[cfg(feature = "const-eval")]
+use aoc22_nostd::{day06, run_const};
+use aoc22_nostd::{entry, expected};
+
+const INPUT: &str = include_str!("../../input/06.txt");
 
 #[entry]
 fn main() -> ! {
-    let input = include_bytes!("../../input/06.txt");
-    let mut parsed = [b' '; 8192];
-    parse(input, &mut parsed);
-
-    let p1 = part1(&parsed);
-    hprintln!("Part 1: {:?}", p1).unwrap();
-
-    let p2 = part2(&parsed);
-    hprintln!("Part 2: {:?}", p2).unwrap();
-
-    // Exit QEMU.
-    debug::exit(debug::EXIT_SUCCESS);
-    unreachable!()
+    #[cfg(feature = "const-eval")]
+    run_const!(Day06, day06::answers(INPUT.as_bytes()), expected!("06"));
+    #[cfg(not(feature = "const-eval"))]
+    run::<Day06>(INPUT, expected!("06"))
 }
@@ -0,0 +1,58 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Times day 6's sliding-count `find_marker` against the pairwise scan it replaced.
+
+#![warn(clippy::all)]
+#![no_std]
+#![no_main]
+
+use aoc22_nostd::{day06, entry, metrics, output, outputln};
+use core::hint::black_box;
+
+const INPUT: &str = include_str!("../../input/06.txt");
+
+/// The old search: keep the last `N` bytes and compare every pair of them at each
+/// position, O(n·N²).
+fn pairwise<const N: usize>(puzzle: &[u8]) -> Option<usize> {
+    let mut window = [0; N];
+    for (idx, &byte) in puzzle.iter().enumerate() {
+        window[idx % N] = byte;
+        let distinct = (0..N).all(|i| (i + 1..N).all(|j| window[i] != window[j]));
+        if idx + 1 >= N && distinct {
+            return Some(idx + 1);
+        }
+    }
+    None
+}
+
+/// Runs `find` on the input and prints its answer and ticks.
+fn time(name: &str, find: fn(&[u8]) -> Option<usize>) -> Option<usize> {
+    let start = metrics::ticks();
+    let end = find(black_box(INPUT.as_bytes()));
+    let ticks = metrics::ticks() - start;
+    outputln!("{:<12} {:>10} ticks: {:?}", name, ticks, end).ok();
+    end
+}
+
+#[entry]
+fn main() -> ! {
+    metrics::start();
+    let same = time("pairwise 4", pairwise::<4>) == time("counts 4", day06::find_marker::<4>)
+        && time("pairwise 14", pairwise::<14>) == time("counts 14", day06::find_marker::<14>);
+    if !same {
+        outputln!("The two searches disagree").ok();
+    }
+    output::exit(same)
+}
@@ -0,0 +1,347 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+#[cfg(feature = "json")]
+use crate::json::JsonObject;
+use crate::parse::{self, u32};
+use crate::top::TopK;
+use crate::{capacities, outputln, Answer, Error, OrBadInput, OrOverflow, Solution};
+use core::cmp::Ordering;
+use heapless::Vec;
+use nom::{
+    character::complete::{line_ending, multispace0},
+    combinator::{all_consuming, opt},
+    multi::fold_many1,
+    sequence::terminated,
+    IResult,
+};
+
+const DAY: u8 = 1;
+/// What an input that is empty or malformed is reported as.
+const BAD_INPUT: &str = "not a list of calorie counts";
+/// How many of the largest totals part 2 adds up.
+const K: usize = capacities::DAY01_TOP_K;
+/// How many elves the `stats` mode can hold.
+const MAX_ELVES: usize = capacities::DAY01_MAX_ELVES;
+/// The `part` in errors from `stats`, which is neither part.
+const STATS: u8 = 0;
+
+pub type Elf = u32;
+
+/// Parses one elf's calorie total, or `None` if the total does not fit in an `Elf`.
+pub fn parse_elf(i: &[u8]) -> IResult<&[u8], Option<Elf>> {
+    let (i, _) = opt(line_ending)(i)?;
+    let (i, elf) = fold_many1(
+        terminated(u32, parse::line_end),
+        || Some(0),
+        |sum: Option<Elf>, val| sum?.checked_add(val),
+    )(i)?;
+    Ok((i, elf))
+}
+
+pub fn part1(i: &str) -> Result<Elf, Error> {
+    all_consuming(terminated(
+        fold_many1(
+            parse_elf,
+            || Ok(0),
+            |most: Result<Elf, Error>, val| {
+                let most = most?;
+                let val = val.or_overflow(DAY, 1, "elf calorie sum")?;
+                if most > val {
+                    Ok(most)
+                } else {
+                    Ok(val)
+                }
+            },
+        ),
+        multispace0,
+    ))(i.as_bytes())
+    .or_bad_input(DAY, BAD_INPUT)?
+}
+
+pub fn part2(i: &str) -> Result<Elf, Error> {
+    let mut top: TopK<K> = TopK::new();
+    let elves = all_consuming(terminated(
+        fold_many1(
+            parse_elf,
+            || Ok(0),
+            |elves: Result<usize, Error>, val| {
+                let elves = elves?;
+                let val = val.or_overflow(DAY, 2, "elf calorie sum")?;
+                top.push(val.into(), elves);
+                Ok(elves + 1)
+            },
+        ),
+        multispace0,
+    ))(i.as_bytes())
+    .or_bad_input(DAY, BAD_INPUT)?;
+    elves?;
+    top.sum()
+        .and_then(|sum| Elf::try_from(sum).ok())
+        .or_overflow(DAY, 2, "top K sum")
+}
+
+/// Per-elf figures for the `stats` mode. Percentiles use the nearest rank, so the
+/// median of an even number of elves is the lower of the middle two.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub elves: usize,
+    /// The `K` largest totals, with the (0-based) index of the elf carrying each.
+    pub top: TopK<K>,
+    pub min: Elf,
+    pub median: Elf,
+    pub p90: Elf,
+}
+
+/// Swaps two totals; does nothing if either is out of range.
+fn swap(totals: &mut [Elf], a: usize, b: usize) {
+    if let (Some(&x), Some(&y)) = (totals.get(a), totals.get(b)) {
+        if let Some(t) = totals.get_mut(a) {
+            *t = y;
+        }
+        if let Some(t) = totals.get_mut(b) {
+            *t = x;
+        }
+    }
+}
+
+/// The total a sort would put at `nth`, found by quickselect: partition around the
+/// middle total, then carry on in the side holding `nth`. It reorders `totals`.
+/// (`select_nth_unstable` does the same, but keeps panic paths the optimizer cannot
+/// remove.)
+fn select_nth(totals: &mut [Elf], nth: usize) -> Option<Elf> {
+    let (mut lo, mut hi) = (0, totals.len());
+    while hi > lo + 1 {
+        let window = totals.get_mut(lo..hi)?;
+        let last = window.len() - 1;
+        swap(window, last / 2, last);
+        let pivot = *window.get(last)?;
+        // Lomuto partition: everything before `store` is below the pivot.
+        let mut store = 0;
+        for i in 0..last {
+            if window.get(i).is_some_and(|&total| total < pivot) {
+                swap(window, i, store);
+                store += 1;
+            }
+        }
+        swap(window, store, last);
+        match (lo + store).cmp(&nth) {
+            Ordering::Equal => break,
+            Ordering::Greater => hi = lo + store,
+            Ordering::Less => lo += store + 1,
+        }
+    }
+    totals.get(nth).copied()
+}
+
+/// The `percent`th percentile of `totals` by nearest rank, found by selection
+/// (reordering `totals`) rather than sorting.
+fn percentile(totals: &mut [Elf], percent: usize) -> Option<Elf> {
+    let rank = (totals.len() * percent).div_ceil(100).max(1);
+    select_nth(totals, rank - 1)
+}
+
+pub fn stats(i: &str) -> Result<Stats, Error> {
+    let mut totals: Vec<Elf, MAX_ELVES> = Vec::new();
+    let mut top: TopK<K> = TopK::new();
+    let elves = all_consuming(terminated(
+        fold_many1(
+            parse_elf,
+            || Ok(0),
+            |elves: Result<usize, Error>, val| {
+                let elves = elves?;
+                let val = val.or_overflow(DAY, STATS, "elf calorie sum")?;
+                totals.push(val).map_err(|_| Error::Capacity {
+                    day: DAY,
+                    buffer: "DAY01_MAX_ELVES",
+                    capacity: MAX_ELVES,
+                    needed: elves + 1,
+                })?;
+                top.push(val.into(), elves);
+                Ok(elves + 1)
+            },
+        ),
+        multispace0,
+    ))(i.as_bytes())
+    .or_bad_input(DAY, BAD_INPUT)??;
+    let no_elves = Error::Parse {
+        day: DAY,
+        what: BAD_INPUT,
+    };
+    Ok(Stats {
+        elves,
+        top,
+        min: totals.iter().copied().min().ok_or(no_elves)?,
+        median: percentile(&mut totals, 50).ok_or(no_elves)?,
+        p90: percentile(&mut totals, 90).ok_or(no_elves)?,
+    })
+}
+
+/// Prints `stats` for the input when the command line asks for it with `stats`.
+pub fn report_stats(i: &str) {
+    if crate::args::with_arg(1, |arg| arg == Some("stats")) {
+        print_stats(stats(i));
+    }
+}
+
+#[cfg(not(feature = "json"))]
+fn print_stats(stats: Result<Stats, Error>) {
+    let stats = match stats {
+        Ok(stats) => stats,
+        Err(e) => {
+            outputln!("No statistics: {}", e).ok();
+            return;
+        }
+    };
+    outputln!("{} elves", stats.elves).ok();
+    for &(calories, index) in stats.top.entries() {
+        outputln!("Elf {} carries {}", index + 1, calories).ok();
+    }
+    outputln!(
+        "Least {}, median {}, 90th percentile {}",
+        stats.min,
+        stats.median,
+        stats.p90
+    )
+    .ok();
+}
+
+/// Like the results, one JSON object per line: one for each top elf, then the rest.
+#[cfg(feature = "json")]
+fn print_stats(stats: Result<Stats, Error>) {
+    let stats = match stats {
+        Ok(stats) => stats,
+        Err(e) => {
+            let mut obj: JsonObject<128> = JsonObject::new();
+            obj.number("day", DAY).display("error", e);
+            if let Ok(json) = obj.finish() {
+                outputln!("{}", json).ok();
+            }
+            return;
+        }
+    };
+    for (rank, &(calories, index)) in stats.top.entries().iter().enumerate() {
+        let mut obj: JsonObject<128> = JsonObject::new();
+        obj.number("day", DAY)
+            .number("rank", rank + 1)
+            .number("elf", index + 1)
+            .number("calories", calories);
+        if let Ok(json) = obj.finish() {
+            outputln!("{}", json).ok();
+        }
+    }
+    let mut obj: JsonObject<128> = JsonObject::new();
+    obj.number("day", DAY)
+        .number("elves", stats.elves)
+        .number("min", stats.min)
+        .number("median", stats.median)
+        .number("p90", stats.p90);
+    if let Ok(json) = obj.finish() {
+        outputln!("{}", json).ok();
+    }
+}
+
+/// `part1` and `part2` as a `const fn`, for the `const-eval` mode. It reads the same
+/// lines as they do and fails the same way.
+pub const fn answers(input: &[u8]) -> [Result<u64, Error>; 2] {
+    const BAD: Error = Error::Parse {
+        day: DAY,
+        what: BAD_INPUT,
+    };
+    let mut top: TopK<K> = TopK::new();
+    let mut elves = 0;
+    // A bad line anywhere wins over an elf that overflowed, as it does in `part1`.
+    let mut overflow = false;
+    let mut i = 0;
+    while !parse::const_only_space(input, i) {
+        // One blank line separates the elves.
+        if input[i] == b'\n' {
+            i += 1;
+        } else if input[i] == b'\r' && i + 1 < input.len() && input[i + 1] == b'\n' {
+            i += 2;
+        }
+        let mut elf: Option<Elf> = Some(0);
+        let mut lines = 0;
+        while lines == 0 || (i < input.len() && input[i].is_ascii_digit()) {
+            let Some((calories, end)) = parse::const_u32(input, i) else {
+                return [Err(BAD); 2];
+            };
+            let Some(next) = parse::const_line_end(input, end) else {
+                return [Err(BAD); 2];
+            };
+            elf = match elf {
+                Some(elf) => elf.checked_add(calories),
+                None => None,
+            };
+            i = next;
+            lines += 1;
+        }
+        match elf {
+            Some(elf) => top.push(elf as u64, elves),
+            None => overflow = true,
+        }
+        elves += 1;
+    }
+    if elves == 0 {
+        return [Err(BAD); 2];
+    }
+    if overflow {
+        return [
+            Err(Error::Overflow {
+                day: DAY,
+                part: 1,
+                op: "elf calorie sum",
+            }),
+            Err(Error::Overflow {
+                day: DAY,
+                part: 2,
+                op: "elf calorie sum",
+            }),
+        ];
+    }
+    let part1 = match top.get(0) {
+        Some((most, _)) => most,
+        None => 0,
+    };
+    match top.sum() {
+        Some(sum) if sum <= Elf::MAX as u64 => [Ok(part1), Ok(sum)],
+        _ => [
+            Ok(part1),
+            Err(Error::Overflow {
+                day: DAY,
+                part: 2,
+                op: "top K sum",
+            }),
+        ],
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = DAY;
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, Error> {
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Result<Answer, Error> {
+        part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &&str) -> Result<Answer, Error> {
+        part2(input).map(Answer::from)
+    }
+}
@@ -0,0 +1,359 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::{parse, Answer, Error, OrBadInput, OrOverflow, Solution};
+use nom::{
+    character::complete::{multispace0, space1},
+    combinator::{all_consuming, map_opt},
+    multi::fold_many1,
+    number::complete::u8 as byte,
+    sequence::{separated_pair, terminated},
+    IResult,
+};
+
+const DAY: u8 = 2;
+/// Reported when the guide is empty or has a line that is not a round.
+const BAD_INPUT: &str = "not a list of rounds";
+
+/// A shape, numbered by its place in the game's cycle.
+pub type Shape = usize;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(kani, derive(kani::Arbitrary))]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+const OUTCOMES: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+#[derive(Debug)]
+pub struct GameRound {
+    them: Shape,
+    us: Shape,
+}
+
+/// A cyclic game of `N` shapes, `N` odd. Each shape beats the `N / 2` shapes just
+/// before it in the cycle and loses to the `N / 2` just after it.
+#[derive(Debug, Clone)]
+pub struct Rules<const N: usize> {
+    /// The strategy guide's letter for each of their shapes.
+    pub their_letters: [u8; N],
+    /// The letter for each of our shapes, in part 1.
+    pub our_letters: [u8; N],
+    /// The letter for each wanted `Outcome`, in part 2.
+    pub outcome_letters: [u8; 3],
+    /// Points for the shape we play.
+    pub shape_points: [u32; N],
+    /// Points for each `Outcome`.
+    pub outcome_points: [u32; 3],
+}
+
+/// The puzzle's game: rock, paper, scissors.
+pub const ROCK_PAPER_SCISSORS: Rules<3> = Rules {
+    their_letters: *b"ABC",
+    our_letters: *b"XYZ",
+    outcome_letters: *b"XYZ",
+    shape_points: [1, 2, 3],
+    outcome_points: [0, 3, 6],
+};
+
+/// Rock, Spock, paper, lizard, scissors, in cycle order: Spock vaporizes rock,
+/// paper disproves Spock and covers rock, and so on around.
+pub const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: Rules<5> = Rules {
+    their_letters: *b"ABCDE",
+    our_letters: *b"VWXYZ",
+    outcome_letters: *b"XYZ",
+    shape_points: [1, 2, 3, 4, 5],
+    outcome_points: [0, 3, 6],
+};
+
+/// Where `letter` is in `letters`.
+const fn position(letters: &[u8], letter: u8) -> Option<usize> {
+    let mut i = 0;
+    while i < letters.len() {
+        if letters[i] == letter {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Parses one of `letters`, as its position.
+fn letter(letters: &[u8]) -> impl Fn(&[u8]) -> IResult<&[u8], usize> + '_ {
+    move |i| map_opt(byte, |l| position(letters, l))(i)
+}
+
+impl<const N: usize> Rules<N> {
+    const ODD: () = assert!(N % 2 == 1, "a cyclic game needs an odd number of shapes");
+
+    /// How `us` does against `them`.
+    pub const fn outcome(&self, them: Shape, us: Shape) -> Outcome {
+        let () = Self::ODD;
+        // How far `us` is ahead of `them` around the cycle.
+        let ahead = (us % N + N - them % N) % N;
+        if ahead == 0 {
+            Outcome::Draw
+        } else if ahead <= N / 2 {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    /// The nearest shape in the cycle that gets `wanted` against `them`.
+    pub const fn shape_for(&self, them: Shape, wanted: Outcome) -> Shape {
+        let () = Self::ODD;
+        match wanted {
+            Outcome::Lose => (them % N + N - 1) % N,
+            Outcome::Draw => them % N,
+            Outcome::Win => (them % N + 1) % N,
+        }
+    }
+
+    pub const fn score(&self, r: &GameRound) -> u32 {
+        let shape = if r.us < N { self.shape_points[r.us] } else { 0 };
+        shape + self.outcome_points[self.outcome(r.them, r.us) as usize]
+    }
+
+    pub fn parse_part1_round<'a>(&self, i: &'a [u8]) -> IResult<&'a [u8], GameRound> {
+        let (i, (them, us)) = separated_pair(
+            letter(&self.their_letters),
+            space1,
+            letter(&self.our_letters),
+        )(i)?;
+        let (i, _) = parse::line_end(i)?;
+        Ok((i, GameRound { them, us }))
+    }
+
+    pub fn parse_part2_round<'a>(&self, i: &'a [u8]) -> IResult<&'a [u8], GameRound> {
+        let outcome = map_opt(letter(&self.outcome_letters), |o| OUTCOMES.get(o).copied());
+        let (i, (them, wanted)) = separated_pair(letter(&self.their_letters), space1, outcome)(i)?;
+        let (i, _) = parse::line_end(i)?;
+        Ok((
+            i,
+            GameRound {
+                them,
+                us: self.shape_for(them, wanted),
+            },
+        ))
+    }
+
+    pub fn part1(&self, i: &str) -> Result<u32, Error> {
+        all_consuming(terminated(
+            fold_many1(
+                |i| self.parse_part1_round(i),
+                || Ok(0),
+                |sum: Result<u32, Error>, r| {
+                    sum?.checked_add(self.score(&r))
+                        .or_overflow(DAY, 1, "score sum")
+                },
+            ),
+            multispace0,
+        ))(i.as_bytes())
+        .or_bad_input(DAY, BAD_INPUT)?
+    }
+
+    pub fn part2(&self, i: &str) -> Result<u32, Error> {
+        all_consuming(terminated(
+            fold_many1(
+                |i| self.parse_part2_round(i),
+                || Ok(0),
+                |sum: Result<u32, Error>, r| {
+                    sum?.checked_add(self.score(&r))
+                        .or_overflow(DAY, 2, "score sum")
+                },
+            ),
+            multispace0,
+        ))(i.as_bytes())
+        .or_bad_input(DAY, BAD_INPUT)?
+    }
+
+    /// `part1` and `part2` as a `const fn`, for the `const-eval` mode. It reads the
+    /// same lines as they do and fails the same way.
+    pub const fn answers(&self, input: &[u8]) -> [Result<u64, Error>; 2] {
+        const BAD: Error = Error::Parse {
+            day: DAY,
+            what: BAD_INPUT,
+        };
+        let mut totals: [Result<u32, Error>; 2] = [Ok(0), Ok(0)];
+        let mut rounds = 0;
+        let mut i = 0;
+        while !parse::const_only_space(input, i) {
+            // The columns are split by spaces or tabs, as `space1` takes them.
+            let mut end = i + 1;
+            while end < input.len() && (input[end] == b' ' || input[end] == b'\t') {
+                end += 1;
+            }
+            if end == i + 1 || end == input.len() {
+                return [Err(BAD); 2];
+            }
+            let Some(them) = position(&self.their_letters, input[i]) else {
+                return [Err(BAD); 2];
+            };
+            // Part 1 reads the second column as our shape, part 2 as the outcome;
+            // a letter can be valid for only one of them.
+            let scores = [
+                match position(&self.our_letters, input[end]) {
+                    Some(us) => Ok(self.score(&GameRound { them, us })),
+                    None => Err(BAD),
+                },
+                match position(&self.outcome_letters, input[end]) {
+                    Some(wanted) if wanted < OUTCOMES.len() => Ok(self.score(&GameRound {
+                        them,
+                        us: self.shape_for(them, OUTCOMES[wanted]),
+                    })),
+                    _ => Err(BAD),
+                },
+            ];
+            let mut part = 0;
+            while part < 2 {
+                // A bad line wins over an earlier overflow, as it does in `part1`.
+                totals[part] = match (totals[part], scores[part]) {
+                    (_, Err(e)) => Err(e),
+                    (Err(e), Ok(_)) => Err(e),
+                    (Ok(total), Ok(score)) => match total.checked_add(score) {
+                        Some(total) => Ok(total),
+                        None => Err(Error::Overflow {
+                            day: DAY,
+                            part: part as u8 + 1,
+                            op: "score sum",
+                        }),
+                    },
+                };
+                part += 1;
+            }
+            let Some(next) = parse::const_line_end(input, end + 1) else {
+                return [Err(BAD); 2];
+            };
+            i = next;
+            rounds += 1;
+        }
+        if rounds == 0 {
+            return [Err(BAD); 2];
+        }
+        let mut answers = [Ok(0); 2];
+        let mut part = 0;
+        while part < 2 {
+            answers[part] = match totals[part] {
+                Ok(total) => Ok(total as u64),
+                Err(e) => Err(e),
+            };
+            part += 1;
+        }
+        answers
+    }
+}
+
+pub fn parse_part1_round(i: &[u8]) -> IResult<&[u8], GameRound> {
+    ROCK_PAPER_SCISSORS.parse_part1_round(i)
+}
+
+pub fn parse_part2_round(i: &[u8]) -> IResult<&[u8], GameRound> {
+    ROCK_PAPER_SCISSORS.parse_part2_round(i)
+}
+
+pub fn part1(i: &str) -> Result<u32, Error> {
+    ROCK_PAPER_SCISSORS.part1(i)
+}
+
+pub fn part2(i: &str) -> Result<u32, Error> {
+    ROCK_PAPER_SCISSORS.part2(i)
+}
+
+/// `part1` and `part2` as a `const fn`, for the `const-eval` mode.
+pub const fn answers(input: &[u8]) -> [Result<u64, Error>; 2] {
+    ROCK_PAPER_SCISSORS.answers(input)
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = DAY;
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, Error> {
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Result<Answer, Error> {
+        part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &&str) -> Result<Answer, Error> {
+        part2(input).map(Answer::from)
+    }
+}
+
+#[cfg(kani)]
+mod verification {
+    use super::*;
+
+    fn any_shape<const N: usize>() -> Shape {
+        let shape: Shape = kani::any();
+        kani::assume(shape < N);
+        shape
+    }
+
+    #[kani::proof]
+    fn score_is_shape_plus_outcome() {
+        let rules = ROCK_PAPER_SCISSORS;
+        let round = GameRound {
+            them: any_shape::<3>(),
+            us: any_shape::<3>(),
+        };
+        // Each hand beats the one before it, cyclically.
+        let expected = match (round.us + 3 - round.them) % 3 {
+            0 => 3,
+            1 => 6,
+            _ => 0,
+        };
+        assert_eq!(rules.score(&round), round.us as u32 + 1 + expected);
+    }
+
+    /// Every shape beats exactly half of the others, and loses to the rest.
+    fn outcomes_are_balanced<const N: usize>(rules: &Rules<N>) {
+        let them = any_shape::<N>();
+        let wins = (0..N)
+            .filter(|&us| rules.outcome(them, us) == Outcome::Win)
+            .count();
+        assert_eq!(wins, N / 2);
+        assert_eq!(rules.outcome(them, them), Outcome::Draw);
+    }
+
+    #[kani::proof]
+    #[kani::unwind(6)]
+    fn outcomes_are_balanced_rpsls() {
+        outcomes_are_balanced(&ROCK_PAPER_SCISSORS_LIZARD_SPOCK);
+    }
+
+    fn shape_for_gets_the_wanted_outcome<const N: usize>(rules: &Rules<N>) {
+        let them = any_shape::<N>();
+        let wanted: Outcome = kani::any();
+        let us = rules.shape_for(them, wanted);
+        assert_eq!(rules.outcome(them, us), wanted);
+    }
+
+    #[kani::proof]
+    fn shape_for_gets_the_wanted_outcome_rps() {
+        shape_for_gets_the_wanted_outcome(&ROCK_PAPER_SCISSORS);
+    }
+
+    #[kani::proof]
+    fn shape_for_gets_the_wanted_outcome_rpsls() {
+        shape_for_gets_the_wanted_outcome(&ROCK_PAPER_SCISSORS_LIZARD_SPOCK);
+    }
+}
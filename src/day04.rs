@@ -0,0 +1,251 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::parse::{self, u32};
+use crate::{Answer, Error, OrBadInput, Solution};
+use nom::{
+    character::complete::{char, multispace0},
+    combinator::all_consuming,
+    multi::fold_many1,
+    sequence::{separated_pair, terminated},
+    IResult,
+};
+
+const DAY: u8 = 4;
+/// Reported when the list is empty or has a line that is not a pair.
+const BAD_INPUT: &str = "not a list of assignment pairs";
+
+pub struct Assignment {
+    low: u32,
+    high: u32,
+}
+
+impl Assignment {
+    const fn contained_within(&self, other: &Assignment) -> bool {
+        self.low <= other.low && self.high >= other.high
+    }
+
+    const fn overlapping(&self, other: &Assignment) -> bool {
+        (self.low >= other.low && self.low <= other.high)
+            || (self.high >= other.low && self.high <= other.high)
+            || (self.low < other.low && self.high > other.high)
+    }
+}
+
+pub fn parse_assignment_pair(i: &[u8]) -> IResult<&[u8], (Assignment, Assignment)> {
+    let (i, ((first_low, first_high), (second_low, second_high))) = separated_pair(
+        separated_pair(u32, char('-'), u32),
+        char(','),
+        separated_pair(u32, char('-'), u32),
+    )(i)?;
+    let (i, _) = parse::line_end(i)?;
+    Ok((
+        i,
+        (
+            Assignment {
+                low: first_low,
+                high: first_high,
+            },
+            Assignment {
+                low: second_low,
+                high: second_high,
+            },
+        ),
+    ))
+}
+
+pub fn part1(i: &str) -> Result<u32, Error> {
+    all_consuming(terminated(
+        fold_many1(
+            parse_assignment_pair,
+            || 0,
+            |mut result, (first, second)| {
+                if first.contained_within(&second) || second.contained_within(&first) {
+                    result += 1;
+                }
+                result
+            },
+        ),
+        multispace0,
+    ))(i.as_bytes())
+    .or_bad_input(DAY, BAD_INPUT)
+}
+
+pub fn part2(i: &str) -> Result<u32, Error> {
+    all_consuming(terminated(
+        fold_many1(
+            parse_assignment_pair,
+            || 0,
+            |mut result, (first, second)| {
+                if first.overlapping(&second) {
+                    result += 1;
+                }
+                result
+            },
+        ),
+        multispace0,
+    ))(i.as_bytes())
+    .or_bad_input(DAY, BAD_INPUT)
+}
+
+/// Reads `low-high` at `input[at..]`, returning it and the index just past it.
+const fn const_assignment(input: &[u8], at: usize) -> Option<(Assignment, usize)> {
+    let Some((low, end)) = parse::const_u32(input, at) else {
+        return None;
+    };
+    if end >= input.len() || input[end] != b'-' {
+        return None;
+    }
+    let Some((high, end)) = parse::const_u32(input, end + 1) else {
+        return None;
+    };
+    Some((Assignment { low, high }, end))
+}
+
+/// `part1` and `part2` as a `const fn`, for the `const-eval` mode. It reads the same
+/// lines as they do and fails the same way.
+pub const fn answers(input: &[u8]) -> [Result<u64, Error>; 2] {
+    const BAD: Error = Error::Parse {
+        day: DAY,
+        what: BAD_INPUT,
+    };
+    let mut contained = 0;
+    let mut overlapping = 0;
+    let mut pairs = 0;
+    let mut i = 0;
+    while !parse::const_only_space(input, i) {
+        let Some((first, end)) = const_assignment(input, i) else {
+            return [Err(BAD); 2];
+        };
+        if end >= input.len() || input[end] != b',' {
+            return [Err(BAD); 2];
+        }
+        let Some((second, end)) = const_assignment(input, end + 1) else {
+            return [Err(BAD); 2];
+        };
+        let Some(next) = parse::const_line_end(input, end) else {
+            return [Err(BAD); 2];
+        };
+        if first.contained_within(&second) || second.contained_within(&first) {
+            contained += 1;
+        }
+        if first.overlapping(&second) {
+            overlapping += 1;
+        }
+        pairs += 1;
+        i = next;
+    }
+    if pairs == 0 {
+        return [Err(BAD); 2];
+    }
+    [Ok(contained), Ok(overlapping)]
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = DAY;
+    type Parsed<'a> = &'a str;
+
+    fn parse(input: &str) -> Result<&str, Error> {
+        Ok(input)
+    }
+
+    fn part1(input: &&str) -> Result<Answer, Error> {
+        part1(input).map(Answer::from)
+    }
+
+    fn part2(input: &&str) -> Result<Answer, Error> {
+        part2(input).map(Answer::from)
+    }
+}
+
+/// `input/04.txt`, parsed by `build.rs`.
+#[cfg(feature = "preparse-04")]
+static PAIRS: &[(Assignment, Assignment)] = &include!(concat!(env!("OUT_DIR"), "/day04_input.rs"));
+
+/// Day 4 with its input parsed at build time (the `preparse-04` mode).
+#[cfg(feature = "preparse-04")]
+pub struct Day04Preparsed;
+
+#[cfg(feature = "preparse-04")]
+impl Solution for Day04Preparsed {
+    const DAY: u8 = DAY;
+    type Parsed<'a> = &'static [(Assignment, Assignment)];
+
+    /// `build.rs` already parsed the same input, so `_input` is not looked at.
+    fn parse(_input: &str) -> Result<&'static [(Assignment, Assignment)], Error> {
+        Ok(PAIRS)
+    }
+
+    fn part1(pairs: &&[(Assignment, Assignment)]) -> Result<Answer, Error> {
+        let contained = pairs
+            .iter()
+            .filter(|(first, second)| {
+                first.contained_within(second) || second.contained_within(first)
+            })
+            .count();
+        Ok(contained.into())
+    }
+
+    fn part2(pairs: &&[(Assignment, Assignment)]) -> Result<Answer, Error> {
+        let overlapping = pairs
+            .iter()
+            .filter(|(first, second)| first.overlapping(second))
+            .count();
+        Ok(overlapping.into())
+    }
+}
+
+#[cfg(kani)]
+mod verification {
+    use super::*;
+
+    fn any_assignment() -> Assignment {
+        let low = kani::any();
+        let high = kani::any();
+        kani::assume(low <= high);
+        Assignment { low, high }
+    }
+
+    fn contains(a: &Assignment, section: u32) -> bool {
+        a.low <= section && section <= a.high
+    }
+
+    /// `a.contained_within(b)` iff every section of `b` is also in `a`.
+    #[kani::proof]
+    fn contained_within_is_superset() {
+        let (a, b) = (any_assignment(), any_assignment());
+        let section = kani::any();
+        if a.contained_within(&b) {
+            assert!(!contains(&b, section) || contains(&a, section));
+        } else {
+            // Then one of `b`'s ends is outside `a`.
+            assert!(!contains(&a, b.low) || !contains(&a, b.high));
+        }
+    }
+
+    /// `a.overlapping(b)` iff some section is in both.
+    #[kani::proof]
+    fn overlapping_is_intersection() {
+        let (a, b) = (any_assignment(), any_assignment());
+        let section = kani::any();
+        if a.overlapping(&b) {
+            let first = a.low.max(b.low);
+            assert!(contains(&a, first) && contains(&b, first));
+        } else {
+            assert!(!(contains(&a, section) && contains(&b, section)));
+        }
+    }
+}
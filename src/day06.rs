@@ -0,0 +1,287 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::{outputln, Answer, Error, Solution};
+
+const DAY: u8 = 6;
+/// The start-of-packet and start-of-message marker lengths.
+const SIZES: [usize; 2] = [4, 14];
+
+/// Watches a datastream one byte at a time for runs of `N` different bytes. Each
+/// byte is O(1) work: it keeps the last `N` bytes, how often each value occurs
+/// among them, and how many different values that makes.
+#[derive(Debug, Clone)]
+pub struct MarkerFinder<const N: usize> {
+    window: [u8; N],
+    pushed: usize,
+    counts: [u16; 256],
+    distinct: usize,
+}
+
+impl<const N: usize> MarkerFinder<N> {
+    pub const fn new() -> Self {
+        MarkerFinder {
+            window: [0; N],
+            pushed: 0,
+            counts: [0; 256],
+            distinct: 0,
+        }
+    }
+
+    /// Takes the next byte, and tells whether it ends a marker: whether the last
+    /// `N` bytes are all different.
+    pub const fn push(&mut self, byte: u8) -> bool {
+        let slot = self.pushed % N;
+        if self.pushed >= N {
+            let old = self.window[slot] as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 0 {
+                self.distinct -= 1;
+            }
+        }
+        self.window[slot] = byte;
+        self.counts[byte as usize] += 1;
+        if self.counts[byte as usize] == 1 {
+            self.distinct += 1;
+        }
+        self.pushed += 1;
+        self.distinct == N
+    }
+
+    /// How many bytes were pushed, which is where a marker found now ends.
+    pub const fn position(&self) -> usize {
+        self.pushed
+    }
+}
+
+impl<const N: usize> Default for MarkerFinder<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Where the first run of `N` different bytes ends, if there is one.
+pub const fn find_marker<const N: usize>(puzzle: &[u8]) -> Option<usize> {
+    let mut finder = MarkerFinder::<N>::new();
+    let mut i = 0;
+    while i < puzzle.len() {
+        if finder.push(puzzle[i]) {
+            return Some(finder.position());
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Everywhere a run of `N` different bytes ends, in order.
+pub fn markers<const N: usize>(bytes: impl IntoIterator<Item = u8>) -> impl Iterator<Item = usize> {
+    let mut finder = MarkerFinder::<N>::new();
+    bytes
+        .into_iter()
+        .filter_map(move |byte| finder.push(byte).then_some(finder.position()))
+}
+
+/// Whether `byte` ends the datastream's line; nothing after it belongs to the stream.
+const fn is_line_end(byte: u8) -> bool {
+    matches!(byte, b'\n' | b'\r')
+}
+
+/// Looks for the start-of-packet and start-of-message markers together, in a single
+/// pass, keeping nothing of the stream but the two windows.
+#[derive(Debug, Clone, Default)]
+pub struct Scanner {
+    packet: MarkerFinder<4>,
+    message: MarkerFinder<14>,
+    ends: [Option<usize>; 2],
+}
+
+impl Scanner {
+    pub const fn new() -> Self {
+        Scanner {
+            packet: MarkerFinder::new(),
+            message: MarkerFinder::new(),
+            ends: [None; 2],
+        }
+    }
+
+    /// Takes the next byte, and tells whether it ends a packet and a message marker.
+    pub const fn push(&mut self, byte: u8) -> [bool; 2] {
+        let found = [self.packet.push(byte), self.message.push(byte)];
+        let mut i = 0;
+        while i < found.len() {
+            if found[i] && self.ends[i].is_none() {
+                self.ends[i] = Some(self.position());
+            }
+            i += 1;
+        }
+        found
+    }
+
+    /// How many bytes were pushed.
+    pub const fn position(&self) -> usize {
+        self.packet.position()
+    }
+
+    /// Whether both markers have been found.
+    pub const fn done(&self) -> bool {
+        self.ends[0].is_some() && self.ends[1].is_some()
+    }
+
+    /// Where the first packet and message markers end, if they were found.
+    pub const fn ends(&self) -> [Option<usize>; 2] {
+        self.ends
+    }
+}
+
+/// Finds both markers in a datastream read one byte at a time from any source, up
+/// to the end of its line. It stops as soon as it has both, except in the
+/// `markers-06` mode, which lists every marker and so reads the whole line.
+pub fn scan(bytes: impl IntoIterator<Item = u8>) -> [Option<usize>; 2] {
+    let all = cfg!(feature = "markers-06");
+    let mut scanner = Scanner::new();
+    let mut counts = [0; 2];
+    for byte in bytes.into_iter().take_while(|&byte| !is_line_end(byte)) {
+        let found = scanner.push(byte);
+        if all {
+            for ((found, count), size) in found.into_iter().zip(&mut counts).zip(SIZES) {
+                if found {
+                    outputln!("{}-byte marker ends at {}", size, scanner.position()).ok();
+                    *count += 1;
+                }
+            }
+        } else if scanner.done() {
+            break;
+        }
+    }
+    if all {
+        for (count, size) in counts.into_iter().zip(SIZES) {
+            outputln!("{} markers of {} different bytes", count, size).ok();
+        }
+    }
+    scanner.ends()
+}
+
+/// The answers, with 0 for a missing marker, as a `const fn` for the `const-eval` mode.
+pub const fn answers(puzzle: &[u8]) -> [Result<u64, Error>; 2] {
+    let mut scanner = Scanner::new();
+    let mut i = 0;
+    while i < puzzle.len() && !is_line_end(puzzle[i]) && !scanner.done() {
+        scanner.push(puzzle[i]);
+        i += 1;
+    }
+    let [part1, part2] = scanner.ends();
+    [
+        Ok(match part1 {
+            Some(end) => end as u64,
+            None => 0,
+        }),
+        Ok(match part2 {
+            Some(end) => end as u64,
+            None => 0,
+        }),
+    ]
+}
+
+/// 0 stands for a missing marker.
+fn answer(end: Option<usize>) -> Result<Answer, Error> {
+    Ok(end.unwrap_or(0).into())
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = DAY;
+    type Parsed<'a> = [Option<usize>; 2];
+
+    /// Both markers are found here, in one pass over the input, so the run's ticks
+    /// and stack are in its parse step rather than in either part.
+    fn parse(input: &str) -> Result<[Option<usize>; 2], Error> {
+        Ok(scan(input.bytes()))
+    }
+
+    fn part1(ends: &[Option<usize>; 2]) -> Result<Answer, Error> {
+        answer(ends[0])
+    }
+
+    fn part2(ends: &[Option<usize>; 2]) -> Result<Answer, Error> {
+        answer(ends[1])
+    }
+}
+
+/// Day 6 reading its datastream from the console instead of flash (the `console-06`
+/// mode): semihosting's stdin by default, UART0 with `uart`, or stdin natively.
+#[cfg(feature = "console-06")]
+pub struct Day06Console;
+
+#[cfg(feature = "console-06")]
+impl Solution for Day06Console {
+    const DAY: u8 = DAY;
+    type Parsed<'a> = [Option<usize>; 2];
+
+    /// Reads the console, so `_input` is not looked at.
+    fn parse(_input: &str) -> Result<[Option<usize>; 2], Error> {
+        Ok(scan(crate::input::console()))
+    }
+
+    fn part1(ends: &[Option<usize>; 2]) -> Result<Answer, Error> {
+        answer(ends[0])
+    }
+
+    fn part2(ends: &[Option<usize>; 2]) -> Result<Answer, Error> {
+        answer(ends[1])
+    }
+}
+
+#[cfg(kani)]
+mod verification {
+    use super::*;
+
+    fn naive_is_marker<const N: usize>(window: &[u8; N]) -> bool {
+        let mut seen = [false; 256];
+        for &b in window {
+            if seen[b as usize] {
+                return false;
+            }
+            seen[b as usize] = true;
+        }
+        true
+    }
+
+    /// Feeds `M` arbitrary bytes to a `MarkerFinder<N>` and checks every answer.
+    fn check_finder<const N: usize, const M: usize>() {
+        let stream: [u8; M] = kani::any();
+        let mut finder = MarkerFinder::<N>::new();
+        for (i, &byte) in stream.iter().enumerate() {
+            let found = finder.push(byte);
+            if i + 1 < N {
+                assert!(!found);
+            } else {
+                let window: &[u8; N] = stream[i + 1 - N..=i].try_into().unwrap();
+                assert_eq!(found, naive_is_marker(window));
+            }
+        }
+    }
+
+    #[kani::proof]
+    #[kani::unwind(7)]
+    fn marker_finder_4_matches_naive() {
+        check_finder::<4, 6>();
+    }
+
+    #[kani::proof]
+    #[kani::unwind(17)]
+    fn marker_finder_14_matches_naive() {
+        check_finder::<14, 16>();
+    }
+}
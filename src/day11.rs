@@ -0,0 +1,707 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::top::TopK;
+use crate::{capacities, outputln, parse, Answer, Error, OrBadInput, OrOverflow, Solution};
+use core::fmt;
+use heapless::{Deque, Vec};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{char, line_ending, multispace0, space0},
+    combinator::{all_consuming, eof, map, opt, value},
+    multi::{fold_many0, fold_many1},
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    IResult,
+};
+
+const DAY: u8 = 11;
+/// Rounds in part 2 of the puzzle.
+const PART2_ROUNDS: u64 = 10_000;
+pub const NUM_MONKEYS: usize = capacities::DAY11_NUM_MONKEYS;
+pub const MAX_ITEMS: usize = capacities::DAY11_MAX_ITEMS;
+
+/// Worry levels, in the order the monkey will inspect them.
+type Items = Deque<u64, MAX_ITEMS>;
+
+pub type Monkeys = Vec<Monkey, NUM_MONKEYS>;
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    num_inspections: usize,
+    items: Items,
+    op: MonkeyOp,
+    modulus: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+/// One side of a monkey's operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Term {
+    Old,
+    Literal(u64),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// `new = <left> <operator> <right>`.
+#[derive(Debug, Clone, Copy)]
+pub struct MonkeyOp {
+    left: Term,
+    operator: Operator,
+    right: Term,
+}
+
+impl Term {
+    fn value(self, old: u64) -> u64 {
+        match self {
+            Term::Old => old,
+            Term::Literal(n) => n,
+        }
+    }
+}
+
+impl fmt::Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Term::Old => f.write_str("old"),
+            Term::Literal(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+impl fmt::Display for MonkeyOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let operator = match self.operator {
+            Operator::Add => '+',
+            Operator::Sub => '-',
+            Operator::Mul => '*',
+            Operator::Div => '/',
+        };
+        write!(f, "{} {} {}", self.left, operator, self.right)
+    }
+}
+
+impl MonkeyOp {
+    /// The new worry level, exactly.
+    fn apply(&self, old: u64, part: u8) -> Result<u64, Error> {
+        let (a, b) = (self.left.value(old), self.right.value(old));
+        match self.operator {
+            Operator::Add => a.checked_add(b).or_overflow(DAY, part, "a + b"),
+            Operator::Sub => a.checked_sub(b).or_overflow(DAY, part, "a - b"),
+            Operator::Mul => a.checked_mul(b).or_overflow(DAY, part, "a * b"),
+            Operator::Div => a.checked_div(b).or_overflow(DAY, part, "a / 0"),
+        }
+    }
+
+    /// Whether the operation still works on remainders. Division does not keep
+    /// them, and subtraction can go below zero (an error in `apply`) without the
+    /// remainders showing it.
+    fn check_reducible(&self, part: u8) -> Result<(), Error> {
+        let what = match self.operator {
+            Operator::Add | Operator::Mul => return Ok(()),
+            Operator::Sub => "subtraction with reduced worry levels",
+            Operator::Div => "division with reduced worry levels",
+        };
+        Err(Error::Unsupported {
+            day: DAY,
+            part,
+            what,
+        })
+    }
+
+    /// The new worry level modulo `modulus`, for when only its remainders matter.
+    ///
+    /// The arithmetic is done in `u128`, where the sum or product of two remainders
+    /// always fits, so any `u64` modulus works.
+    fn apply_mod(&self, old: u64, modulus: u64, part: u8) -> Result<u64, Error> {
+        self.check_reducible(part)?;
+        let modulus = u128::from(modulus);
+        let term = |t: Term| {
+            u128::from(t.value(old))
+                .checked_rem(modulus)
+                .or_overflow(DAY, part, "a % 0")
+        };
+        let (a, b) = (term(self.left)?, term(self.right)?);
+        let new = match self.operator {
+            Operator::Add => a.checked_add(b),
+            Operator::Mul => a.checked_mul(b),
+            // Rejected by `check_reducible`.
+            Operator::Sub | Operator::Div => None,
+        };
+        new.and_then(|new| new.checked_rem(modulus))
+            .and_then(|new| u64::try_from(new).ok())
+            .or_overflow(DAY, part, "a op b (mod product)")
+    }
+}
+
+fn bad_input(what: &'static str) -> Error {
+    Error::Parse { day: DAY, what }
+}
+
+fn full(buffer: &'static str, capacity: usize, needed: usize) -> Error {
+    Error::Capacity {
+        day: DAY,
+        buffer,
+        capacity,
+        needed,
+    }
+}
+
+/// Adds `worry` to the end of `items`.
+fn push(items: &mut Items, worry: u64) -> Result<(), Error> {
+    items
+        .push_back(worry)
+        .map_err(|_| full("DAY11_MAX_ITEMS", MAX_ITEMS, MAX_ITEMS + 1))
+}
+
+/// One line of a monkey's description: `label`, then `value`, then the end of the line.
+fn line<'a, O>(
+    label: &'static str,
+    value: impl FnMut(&'a [u8]) -> IResult<&'a [u8], O>,
+) -> impl FnMut(&'a [u8]) -> IResult<&'a [u8], O> {
+    delimited(
+        pair(space0, tag(label)),
+        preceded(space0, value),
+        pair(space0, alt((line_ending, eof))),
+    )
+}
+
+/// `79, 98`: the starting items, or an error if there are more than `MAX_ITEMS`.
+fn items(i: &[u8]) -> IResult<&[u8], Result<Items, Error>> {
+    let (i, first) = opt(parse::u64)(i)?;
+    let Some(first) = first else {
+        return Ok((i, Ok(Items::new())));
+    };
+    fold_many0(
+        preceded(pair(char(','), space0), parse::u64),
+        move || {
+            let mut items = Items::new();
+            // Cannot fail: `build.rs` keeps `MAX_ITEMS` above 0.
+            let _ = items.push_back(first);
+            Ok(items)
+        },
+        |items: Result<Items, Error>, item| {
+            let mut items = items?;
+            push(&mut items, item)?;
+            Ok(items)
+        },
+    )(i)
+}
+
+/// `old` or a number.
+fn term(i: &[u8]) -> IResult<&[u8], Term> {
+    alt((value(Term::Old, tag("old")), map(parse::u64, Term::Literal)))(i)
+}
+
+/// `new = old * 19`.
+fn operation(i: &[u8]) -> IResult<&[u8], MonkeyOp> {
+    let operator = alt((
+        value(Operator::Add, char('+')),
+        value(Operator::Sub, char('-')),
+        value(Operator::Mul, char('*')),
+        value(Operator::Div, char('/')),
+    ));
+    map(
+        preceded(
+            tuple((tag("new"), space0, char('='), space0)),
+            tuple((term, delimited(space0, operator, space0), term)),
+        ),
+        |(left, operator, right)| MonkeyOp {
+            left,
+            operator,
+            right,
+        },
+    )(i)
+}
+
+/// Parses one monkey's description. Gives its number, and the monkey or why it
+/// cannot be one.
+pub fn parse_monkey(i: &[u8]) -> IResult<&[u8], (usize, Result<Monkey, Error>)> {
+    let (i, number) = line("Monkey", terminated(parse::usize, char(':')))(i)?;
+    let (i, items) = line("Starting items:", items)(i)?;
+    let (i, op) = line("Operation:", operation)(i)?;
+    let (i, modulus) = line("Test: divisible by", parse::u64)(i)?;
+    let (i, if_true) = line("If true: throw to monkey", parse::usize)(i)?;
+    let (i, if_false) = line("If false: throw to monkey", parse::usize)(i)?;
+    let monkey = match (items, modulus) {
+        (Err(e), _) => Err(e),
+        (_, 0) => Err(bad_input("divisible by 0")),
+        _ if op.operator == Operator::Div && op.right == Term::Literal(0) => {
+            Err(bad_input("division by 0"))
+        }
+        (Ok(items), modulus) => Ok(Monkey {
+            num_inspections: 0,
+            items,
+            op,
+            modulus,
+            if_true,
+            if_false,
+        }),
+    };
+    Ok((i, (number, monkey)))
+}
+
+/// Checks that every monkey throws to another monkey that exists.
+fn check_targets(monkeys: &[Monkey]) -> Result<(), Error> {
+    for (i, monkey) in monkeys.iter().enumerate() {
+        for target in [monkey.if_true, monkey.if_false] {
+            if target >= monkeys.len() {
+                return Err(bad_input("no such monkey"));
+            }
+            // The thrower would never run out of items.
+            if target == i {
+                return Err(bad_input("monkey throws to itself"));
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn parse(input: &[u8]) -> Result<Monkeys, Error> {
+    let mut monkeys = Monkeys::new();
+    // Monkeys are separated by blank lines.
+    let parsed = all_consuming(terminated(
+        fold_many1(
+            preceded(multispace0, parse_monkey),
+            || Ok(0),
+            |count: Result<usize, Error>, (number, monkey)| {
+                let count = count?;
+                if number != count {
+                    return Err(bad_input("monkeys out of order"));
+                }
+                // Once `monkeys` is full, carry on counting, to report how many there are.
+                let _ = monkeys.push(monkey?);
+                Ok(count + 1)
+            },
+        ),
+        multispace0,
+    ))(input)
+    .or_bad_input(DAY, "unrecognized monkey description")?;
+    let count = parsed?;
+    if count > NUM_MONKEYS {
+        return Err(full("DAY11_NUM_MONKEYS", NUM_MONKEYS, count));
+    }
+    check_targets(&monkeys)?;
+    Ok(monkeys)
+}
+
+/// Hands `worry` to monkey `target`.
+fn throw(monkeys: &mut [Monkey], target: usize, worry: u64) -> Result<(), Error> {
+    let target = monkeys
+        .get_mut(target)
+        .ok_or_else(|| bad_input("no such monkey"))?;
+    push(&mut target.items, worry)
+}
+
+/// With `trace-11-throws`, starts a monkey's turn in the first round's narrative.
+fn trace_turn(round: u64, monkey: usize) {
+    if cfg!(feature = "trace-11-throws") && round == 1 {
+        outputln!("Monkey {}:", monkey).ok();
+    }
+}
+
+/// With `trace-11-throws`, tells how `monkey` handled one item in the first round,
+/// the way the puzzle does. `bored` is the worry level after part 1's division by 3.
+fn trace_throw(round: u64, monkey: &Monkey, old: u64, new: u64, bored: Option<u64>, target: usize) {
+    if !cfg!(feature = "trace-11-throws") || round != 1 {
+        return;
+    }
+    outputln!("  Monkey inspects an item with a worry level of {}.", old).ok();
+    let op = monkey.op;
+    match (op.left, op.operator, op.right) {
+        (Term::Old, Operator::Mul, Term::Old) => {
+            outputln!("    Worry level is multiplied by itself to {}.", new)
+        }
+        (Term::Old, Operator::Mul, Term::Literal(n)) => {
+            outputln!("    Worry level is multiplied by {} to {}.", n, new)
+        }
+        (Term::Old, Operator::Add, Term::Literal(n)) => {
+            outputln!("    Worry level increases by {} to {}.", n, new)
+        }
+        _ => outputln!("    Worry level becomes {} = {}.", op, new),
+    }
+    .ok();
+    let worry = match bored {
+        Some(worry) => {
+            outputln!(
+                "    Monkey gets bored with item. Worry level is divided by 3 to {}.",
+                worry
+            )
+            .ok();
+            worry
+        }
+        None => new,
+    };
+    let not = if worry.checked_rem(monkey.modulus) == Some(0) {
+        ""
+    } else {
+        " not"
+    };
+    outputln!(
+        "    Current worry level is{} divisible by {}.",
+        not,
+        monkey.modulus
+    )
+    .ok();
+    outputln!(
+        "    Item with worry level {} is thrown to monkey {}.",
+        worry,
+        target
+    )
+    .ok();
+}
+
+/// With `trace-11`, prints the inspection counts after the rounds the puzzle shows
+/// them for: 1, 20 and every 1000th.
+fn trace_round(part: u8, round: u64, monkeys: &[Monkey]) {
+    if !cfg!(feature = "trace-11") || !(round == 1 || round == 20 || round.is_multiple_of(1000)) {
+        return;
+    }
+    outputln!("== After round {} (part {}) ==", round, part).ok();
+    for (m, monkey) in monkeys.iter().enumerate() {
+        outputln!(
+            "Monkey {} inspected items {} times.",
+            m,
+            monkey.num_inspections
+        )
+        .ok();
+    }
+    outputln!().ok();
+}
+
+/// Multiplies the two largest inspection counts together.
+fn monkey_business(monkeys: &[Monkey], part: u8) -> Result<u64, Error> {
+    let mut top: TopK<2> = TopK::new();
+    for (m, monkey) in monkeys.iter().enumerate() {
+        top.push(monkey.num_inspections as u64, m);
+    }
+    top.entries()
+        .iter()
+        .try_fold(1, |product: u64, &(count, _)| product.checked_mul(count))
+        .or_overflow(DAY, part, "monkey business")
+}
+
+pub fn part1(parsed: &Monkeys) -> Result<u64, Error> {
+    let mut monkeys = parsed.clone();
+
+    for round in 1..=20 {
+        for m in 0..monkeys.len() {
+            trace_turn(round, m);
+            while let Some(monkey) = monkeys.get_mut(m) {
+                let Some(w) = monkey.items.pop_front() else {
+                    break;
+                };
+                monkey.num_inspections += 1;
+                let new = monkey.op.apply(w, 1)?;
+                let worry = new / 3;
+                let target = if worry.checked_rem(monkey.modulus) == Some(0) {
+                    monkey.if_true
+                } else {
+                    monkey.if_false
+                };
+                trace_throw(round, monkey, w, new, Some(worry), target);
+                throw(&mut monkeys, target, worry)?;
+            }
+        }
+        trace_round(1, round, &monkeys);
+    }
+
+    monkey_business(&monkeys, 1)
+}
+
+/// The product of all the moduli, which part 2 keeps worry levels below. Every
+/// operation must still work on the remainders.
+fn mod_product(monkeys: &[Monkey]) -> Result<u64, Error> {
+    for monkey in monkeys {
+        monkey.op.check_reducible(2)?;
+    }
+    monkeys
+        .iter()
+        .try_fold(1, |product: u64, m| product.checked_mul(m.modulus))
+        .or_overflow(DAY, 2, "modulus product")
+}
+
+pub fn part2(parsed: &Monkeys) -> Result<u64, Error> {
+    let mut monkeys = parsed.clone();
+    let mod_product = mod_product(&monkeys)?;
+
+    for round in 1..=PART2_ROUNDS {
+        for m in 0..monkeys.len() {
+            trace_turn(round, m);
+            while let Some(monkey) = monkeys.get_mut(m) {
+                let Some(w) = monkey.items.pop_front() else {
+                    break;
+                };
+                monkey.num_inspections += 1;
+                // Every modulus divides `mod_product`, so the tests still work on
+                // the remainder, and it stays small.
+                let worry = monkey.op.apply_mod(w, mod_product, 2)?;
+                let target = if worry.checked_rem(monkey.modulus) == Some(0) {
+                    monkey.if_true
+                } else {
+                    monkey.if_false
+                };
+                trace_throw(round, monkey, w, worry, None, target);
+                throw(&mut monkeys, target, worry)?;
+            }
+        }
+        trace_round(2, round, &monkeys);
+    }
+
+    monkey_business(&monkeys, 2)
+}
+
+/// Where an item is at the start of a round: which monkey has it, and its worry level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ItemState {
+    monkey: usize,
+    worry: u64,
+}
+
+type Inspections = [u64; NUM_MONKEYS];
+
+/// Plays one round of part 2 for a single item, counting its inspections. Items
+/// never affect each other, so each one can be followed on its own.
+fn item_round(
+    monkeys: &[Monkey],
+    mod_product: u64,
+    mut state: ItemState,
+    inspections: &mut Inspections,
+) -> Result<ItemState, Error> {
+    loop {
+        let monkey = monkeys
+            .get(state.monkey)
+            .ok_or_else(|| bad_input("no such monkey"))?;
+        if let Some(count) = inspections.get_mut(state.monkey) {
+            *count += 1;
+        }
+        let worry = monkey.op.apply_mod(state.worry, mod_product, 2)?;
+        let target = if worry.checked_rem(monkey.modulus) == Some(0) {
+            monkey.if_true
+        } else {
+            monkey.if_false
+        };
+        let next = ItemState {
+            monkey: target,
+            worry,
+        };
+        // A monkey later in the order still gets its turn this round.
+        if target < state.monkey {
+            return Ok(next);
+        }
+        state = next;
+    }
+}
+
+/// Plays `rounds` rounds of part 2 for a single item.
+fn item_rounds(
+    monkeys: &[Monkey],
+    mod_product: u64,
+    mut state: ItemState,
+    rounds: u64,
+    inspections: &mut Inspections,
+) -> Result<ItemState, Error> {
+    for _ in 0..rounds {
+        state = item_round(monkeys, mod_product, state, inspections)?;
+    }
+    Ok(state)
+}
+
+/// Finds when an item's rounds start repeating, with Brent's algorithm: the rounds
+/// before the cycle, and the cycle's length. Gives up after `limit` rounds, since
+/// playing them all is no slower then.
+fn find_cycle(
+    monkeys: &[Monkey],
+    mod_product: u64,
+    start: ItemState,
+    limit: u64,
+) -> Result<Option<(u64, u64)>, Error> {
+    let mut ignored = [0; NUM_MONKEYS];
+    let mut round = |state| item_round(monkeys, mod_product, state, &mut ignored);
+
+    // The length: the tortoise waits at powers of two for the hare to come round.
+    let (mut power, mut length) = (1u64, 1u64);
+    let mut tortoise = start;
+    let mut hare = round(start)?;
+    let mut played = 1;
+    while tortoise != hare {
+        if played > limit {
+            return Ok(None);
+        }
+        if power == length {
+            tortoise = hare;
+            power = power.saturating_mul(2);
+            length = 0;
+        }
+        hare = round(hare)?;
+        length += 1;
+        played += 1;
+    }
+
+    // The start: with the hare `length` rounds ahead, they meet where the cycle begins.
+    let (mut tortoise, mut hare) = (start, start);
+    for _ in 0..length {
+        hare = round(hare)?;
+    }
+    let mut tail = 0;
+    while tortoise != hare {
+        tortoise = round(tortoise)?;
+        hare = round(hare)?;
+        tail += 1;
+    }
+    Ok(Some((tail, length)))
+}
+
+/// Part 2 for any number of rounds. Each item is followed on its own, and once its
+/// rounds repeat, whole cycles are counted at once.
+/// The counts can get big enough that their product only fits in a `u128`.
+pub fn part2_cycles(parsed: &Monkeys, rounds: u64) -> Result<u128, Error> {
+    let mod_product = mod_product(parsed)?;
+    let mut inspections = [0; NUM_MONKEYS];
+    for (m, monkey) in parsed.iter().enumerate() {
+        for &worry in monkey.items.iter() {
+            let start = ItemState { monkey: m, worry };
+            let Some((tail, length)) = find_cycle(parsed, mod_product, start, rounds)? else {
+                item_rounds(parsed, mod_product, start, rounds, &mut inspections)?;
+                continue;
+            };
+            let state = item_rounds(
+                parsed,
+                mod_product,
+                start,
+                tail.min(rounds),
+                &mut inspections,
+            )?;
+            if rounds <= tail {
+                continue;
+            }
+            let mut cycle = [0; NUM_MONKEYS];
+            let state = item_rounds(parsed, mod_product, state, length, &mut cycle)?;
+            // `length` is at least 1.
+            let cycles = (rounds - tail).checked_div(length).unwrap_or(0);
+            let rest = (rounds - tail).checked_rem(length).unwrap_or(0);
+            for (total, count) in inspections.iter_mut().zip(cycle) {
+                *total = count
+                    .checked_mul(cycles)
+                    .and_then(|count| total.checked_add(count))
+                    .or_overflow(DAY, 2, "inspections")?;
+            }
+            item_rounds(parsed, mod_product, state, rest, &mut inspections)?;
+        }
+    }
+
+    let mut top: TopK<2> = TopK::new();
+    for (m, count) in inspections.into_iter().enumerate() {
+        top.push(count, m);
+    }
+    Ok(top
+        .entries()
+        .iter()
+        .map(|&(count, _)| u128::from(count))
+        .product())
+}
+
+/// `input/11.txt`, parsed by `build.rs`: each monkey, with its starting items kept
+/// apart because a `Deque` cannot be filled in a `static`.
+#[cfg(feature = "preparse-11")]
+static MONKEYS: &[(Monkey, &[u64])] = &include!(concat!(env!("OUT_DIR"), "/day11_input.rs"));
+
+/// Day 11 with its input parsed at build time (the `preparse-11` mode).
+#[cfg(feature = "preparse-11")]
+pub struct Day11Preparsed;
+
+#[cfg(feature = "preparse-11")]
+impl Solution for Day11Preparsed {
+    const DAY: u8 = DAY;
+    type Parsed<'a> = Monkeys;
+
+    /// `build.rs` already parsed the same input, so `_input` is not looked at.
+    fn parse(_input: &str) -> Result<Monkeys, Error> {
+        let mut monkeys = Monkeys::new();
+        for (monkey, items) in MONKEYS {
+            let mut monkey = monkey.clone();
+            for &item in *items {
+                push(&mut monkey.items, item)?;
+            }
+            monkeys
+                .push(monkey)
+                .map_err(|_| full("DAY11_NUM_MONKEYS", NUM_MONKEYS, MONKEYS.len()))?;
+        }
+        check_targets(&monkeys)?;
+        Ok(monkeys)
+    }
+
+    fn part1(monkeys: &Monkeys) -> Result<Answer, Error> {
+        part1(monkeys).map(Answer::from)
+    }
+
+    fn part2(monkeys: &Monkeys) -> Result<Answer, Error> {
+        part2(monkeys).map(Answer::from)
+    }
+}
+
+/// Day 11 with part 2 worked out item by item (the `cycles-11` mode). The round
+/// count is the first command-line argument, or the puzzle's 10000.
+#[cfg(feature = "cycles-11")]
+pub struct Day11Cycles;
+
+#[cfg(feature = "cycles-11")]
+impl Solution for Day11Cycles {
+    const DAY: u8 = DAY;
+    type Parsed<'a> = (Monkeys, u64);
+
+    fn parse(input: &str) -> Result<(Monkeys, u64), Error> {
+        let rounds = crate::args::with_arg(1, |arg| match arg.map(|a| parse::u64(a.as_bytes())) {
+            None => Ok(PART2_ROUNDS),
+            Some(Ok((b"", rounds))) => Ok(rounds),
+            Some(_) => Err(bad_input("round count is not a number")),
+        })?;
+        Ok((parse(input.as_bytes())?, rounds))
+    }
+
+    fn part1((monkeys, _): &(Monkeys, u64)) -> Result<Answer, Error> {
+        part1(monkeys).map(Answer::from)
+    }
+
+    fn part2((monkeys, rounds): &(Monkeys, u64)) -> Result<Answer, Error> {
+        part2_cycles(monkeys, *rounds).map(Answer::from)
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = DAY;
+    type Parsed<'a> = Monkeys;
+
+    fn parse(input: &str) -> Result<Monkeys, Error> {
+        parse(input.as_bytes())
+    }
+
+    fn part1(monkeys: &Monkeys) -> Result<Answer, Error> {
+        part1(monkeys).map(Answer::from)
+    }
+
+    fn part2(monkeys: &Monkeys) -> Result<Answer, Error> {
+        part2(monkeys).map(Answer::from)
+    }
+}
@@ -0,0 +1,87 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use core::fmt;
+
+/// Why a solution could not produce an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// An arithmetic operation did not fit in its integer type. `part` is 0 for work
+    /// outside the two parts, such as day 1's statistics.
+    Overflow { day: u8, part: u8, op: &'static str },
+    /// The puzzle input is malformed.
+    Parse { day: u8, what: &'static str },
+    /// The input is valid, but the part's method cannot handle it.
+    Unsupported {
+        day: u8,
+        part: u8,
+        what: &'static str,
+    },
+    /// A fixed-size buffer is too small. `buffer` is its name in `capacities.conf`.
+    Capacity {
+        day: u8,
+        buffer: &'static str,
+        capacity: usize,
+        needed: usize,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Overflow { day, part: 0, op } => {
+                write!(f, "day {:02}: overflow in `{}`", day, op)
+            }
+            Error::Overflow { day, part, op } => {
+                write!(f, "day {:02} part {}: overflow in `{}`", day, part, op)
+            }
+            Error::Parse { day, what } => write!(f, "day {:02}: bad input: {}", day, what),
+            Error::Unsupported { day, part, what } => {
+                write!(f, "day {:02} part {}: unsupported: {}", day, part, what)
+            }
+            Error::Capacity {
+                day,
+                buffer,
+                capacity,
+                needed,
+            } => write!(
+                f,
+                "day {:02}: {} is {}, but at least {} is needed",
+                day, buffer, capacity, needed
+            ),
+        }
+    }
+}
+
+/// Turns the `None` from a `checked_*` operation into an `Error::Overflow`.
+pub trait OrOverflow<T> {
+    fn or_overflow(self, day: u8, part: u8, op: &'static str) -> Result<T, Error>;
+}
+
+impl<T> OrOverflow<T> for Option<T> {
+    fn or_overflow(self, day: u8, part: u8, op: &'static str) -> Result<T, Error> {
+        self.ok_or(Error::Overflow { day, part, op })
+    }
+}
+
+/// Turns a failed `nom` parse into an `Error::Parse`, keeping just the output.
+pub trait OrBadInput<T> {
+    fn or_bad_input(self, day: u8, what: &'static str) -> Result<T, Error>;
+}
+
+impl<I, T, E> OrBadInput<T> for nom::IResult<I, T, E> {
+    fn or_bad_input(self, day: u8, what: &'static str) -> Result<T, Error> {
+        self.map(|(_, t)| t).map_err(|_| Error::Parse { day, what })
+    }
+}
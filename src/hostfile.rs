@@ -0,0 +1,90 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Files on the host machine, reached through semihosting (or directly, natively).
+
+use core::fmt;
+#[cfg(not(feature = "host"))]
+use cortex_m_semihosting::{nr, syscall};
+
+/// A host file opened for writing. It is closed when dropped.
+#[cfg(not(feature = "host"))]
+pub struct HostFile {
+    fd: usize,
+}
+
+#[cfg(not(feature = "host"))]
+impl HostFile {
+    /// Creates `path` on the host, truncating any existing file. The path is relative
+    /// to QEMU's working directory and its parent directory must already exist.
+    pub fn create(path: &str) -> Result<HostFile, ()> {
+        // SYS_OPEN wants a NUL-terminated name plus its length without the NUL.
+        let mut name: heapless::Vec<u8, 64> = heapless::Vec::new();
+        name.extend_from_slice(path.as_bytes())?;
+        name.push(0).map_err(|_| ())?;
+        // SAFETY: `name` is NUL-terminated and outlives the call.
+        let fd = unsafe { syscall!(OPEN, name.as_ptr(), nr::open::W_TRUNC, path.len()) };
+        match fd as isize {
+            -1 => Err(()),
+            fd => Ok(HostFile { fd: fd as usize }),
+        }
+    }
+
+    pub fn write_all(&mut self, mut buf: &[u8]) -> Result<(), ()> {
+        while !buf.is_empty() {
+            // SAFETY: `buf` is valid for reads of `buf.len()` bytes during the call.
+            // SYS_WRITE returns the number of bytes it did *not* write.
+            let left = unsafe { syscall!(WRITE, self.fd, buf.as_ptr(), buf.len()) };
+            match left {
+                0 => return Ok(()),
+                n if n < buf.len() => buf = &buf[buf.len() - n..],
+                _ => return Err(()),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Write for HostFile {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_all(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+#[cfg(not(feature = "host"))]
+impl Drop for HostFile {
+    fn drop(&mut self) {
+        // SAFETY: `fd` came from SYS_OPEN and is closed exactly once.
+        unsafe {
+            syscall!(CLOSE, self.fd);
+        }
+    }
+}
+
+#[cfg(feature = "host")]
+pub struct HostFile {
+    file: std::fs::File,
+}
+
+#[cfg(feature = "host")]
+impl HostFile {
+    pub fn create(path: &str) -> Result<HostFile, ()> {
+        let file = std::fs::File::create(path).map_err(|_| ())?;
+        Ok(HostFile { file })
+    }
+
+    pub fn write_all(&mut self, buf: &[u8]) -> Result<(), ()> {
+        std::io::Write::write_all(&mut self.file, buf).map_err(|_| ())
+    }
+}
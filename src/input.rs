@@ -0,0 +1,135 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Puzzle input read from the console, a byte at a time, for days that can stream
+//! it. The source follows the output sink: semihosting by default, `uart` for
+//! UART0, or `host` for the host's stdin.
+
+/// Reads the host's stdin through semihosting, a small chunk at a time.
+#[cfg(not(any(feature = "uart", feature = "host")))]
+pub struct Semihosting {
+    fd: Option<usize>,
+    buf: [u8; 64],
+    len: usize,
+    next: usize,
+}
+
+#[cfg(not(any(feature = "uart", feature = "host")))]
+impl Semihosting {
+    fn open() -> Self {
+        use cortex_m_semihosting::{nr, syscall};
+        // ":tt" opened for reading is the debugger's (or QEMU's) stdin.
+        let name = b":tt\0";
+        // SAFETY: `name` is NUL-terminated and outlives the call.
+        let fd = unsafe { syscall!(OPEN, name.as_ptr(), nr::open::R, name.len() - 1) };
+        Semihosting {
+            fd: (fd as isize != -1).then_some(fd),
+            buf: [0; 64],
+            len: 0,
+            next: 0,
+        }
+    }
+}
+
+#[cfg(not(any(feature = "uart", feature = "host")))]
+impl Iterator for Semihosting {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.next == self.len {
+            let fd = self.fd?;
+            // SAFETY: `buf` is valid for writes of `buf.len()` bytes during the call.
+            // SYS_READ returns the number of bytes it did *not* read, so a read of
+            // nothing is the end of the stream.
+            let left = unsafe {
+                cortex_m_semihosting::syscall!(READ, fd, self.buf.as_mut_ptr(), self.buf.len())
+            };
+            self.len = self.buf.len().checked_sub(left)?;
+            self.next = 0;
+            if self.len == 0 {
+                return None;
+            }
+        }
+        let byte = *self.buf.get(..self.len)?.get(self.next)?;
+        self.next += 1;
+        Some(byte)
+    }
+}
+
+#[cfg(not(any(feature = "uart", feature = "host")))]
+impl Drop for Semihosting {
+    fn drop(&mut self) {
+        if let Some(fd) = self.fd {
+            // SAFETY: `fd` came from SYS_OPEN and is closed exactly once.
+            unsafe {
+                cortex_m_semihosting::syscall!(CLOSE, fd);
+            }
+        }
+    }
+}
+
+/// Receives on the LM3S6965's UART0. It never runs out: the reader decides where
+/// the input ends.
+#[cfg(feature = "uart")]
+pub struct Uart0;
+
+#[cfg(feature = "uart")]
+impl Uart0 {
+    const DR: *const u32 = 0x4000_C000 as *const u32;
+    const FR: *const u32 = 0x4000_C018 as *const u32;
+    const CTL: *mut u32 = 0x4000_C030 as *mut u32;
+    const FR_RXFE: u32 = 1 << 4;
+    const CTL_RXE: u32 = 1 << 9;
+
+    fn open() -> Self {
+        // The output side gates the clock and enables the UART.
+        <crate::output::Uart0 as crate::output::Sink>::open();
+        // SAFETY: only sets UART0's receive enable bit.
+        unsafe {
+            let ctl = core::ptr::read_volatile(Self::CTL);
+            core::ptr::write_volatile(Self::CTL, ctl | Self::CTL_RXE);
+        }
+        Uart0
+    }
+}
+
+#[cfg(feature = "uart")]
+impl Iterator for Uart0 {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        // SAFETY: DR and FR are UART0's data and flag registers.
+        unsafe {
+            while core::ptr::read_volatile(Self::FR) & Self::FR_RXFE != 0 {}
+            Some(core::ptr::read_volatile(Self::DR) as u8)
+        }
+    }
+}
+
+/// The console's bytes, from the source selected by cargo features.
+#[cfg(not(any(feature = "uart", feature = "host")))]
+pub fn console() -> impl Iterator<Item = u8> {
+    Semihosting::open()
+}
+
+#[cfg(feature = "uart")]
+pub fn console() -> impl Iterator<Item = u8> {
+    Uart0::open()
+}
+
+#[cfg(feature = "host")]
+pub fn console() -> impl Iterator<Item = u8> {
+    use std::io::Read;
+    std::io::stdin().lock().bytes().map_while(Result::ok)
+}
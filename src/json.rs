@@ -0,0 +1,111 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! A tiny JSON object writer over a fixed-size buffer.
+
+use core::fmt::{self, Display, Write};
+use heapless::String;
+
+/// Builds a single-line JSON object. Any write that does not fit in `N` bytes
+/// makes `finish` fail rather than producing truncated JSON.
+pub struct JsonObject<const N: usize> {
+    buf: String<N>,
+    result: fmt::Result,
+}
+
+impl<const N: usize> JsonObject<N> {
+    pub fn new() -> Self {
+        let mut obj = JsonObject {
+            buf: String::new(),
+            result: Ok(()),
+        };
+        obj.result = obj.buf.write_char('{');
+        obj
+    }
+
+    fn key(&mut self, key: &str) {
+        if self.buf.len() > 1 {
+            self.write_raw(",");
+        }
+        self.write_str(key);
+        self.write_raw(":");
+    }
+
+    fn write_raw(&mut self, s: &str) {
+        if self.result.is_ok() {
+            self.result = self.buf.write_str(s);
+        }
+    }
+
+    fn write_str(&mut self, s: &str) {
+        self.write_raw("\"");
+        for c in s.chars() {
+            if self.result.is_err() {
+                return;
+            }
+            self.result = match c {
+                '"' => self.buf.write_str("\\\""),
+                '\\' => self.buf.write_str("\\\\"),
+                '\n' => self.buf.write_str("\\n"),
+                '\r' => self.buf.write_str("\\r"),
+                '\t' => self.buf.write_str("\\t"),
+                c if (c as u32) < 0x20 => write!(self.buf, "\\u{:04x}", c as u32),
+                c => self.buf.write_char(c),
+            };
+        }
+        self.write_raw("\"");
+    }
+
+    /// Adds a number (or anything else whose `Display` is a valid JSON literal).
+    pub fn number(&mut self, key: &str, value: impl Display) -> &mut Self {
+        self.key(key);
+        if self.result.is_ok() {
+            self.result = write!(self.buf, "{}", value);
+        }
+        self
+    }
+
+    pub fn string(&mut self, key: &str, value: &str) -> &mut Self {
+        self.key(key);
+        self.write_str(value);
+        self
+    }
+
+    /// Adds a string rendered from `Display`, escaped like `string`.
+    pub fn display(&mut self, key: &str, value: impl Display) -> &mut Self {
+        let mut rendered: String<N> = String::new();
+        if write!(rendered, "{}", value).is_err() {
+            self.result = Err(fmt::Error);
+        }
+        self.string(key, &rendered)
+    }
+
+    pub fn null(&mut self, key: &str) -> &mut Self {
+        self.key(key);
+        self.write_raw("null");
+        self
+    }
+
+    /// Closes the object and returns it, or an error if it overflowed the buffer.
+    pub fn finish(&mut self) -> Result<&str, fmt::Error> {
+        self.write_raw("}");
+        self.result.map(|_| self.buf.as_str())
+    }
+}
+
+impl<const N: usize> Default for JsonObject<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
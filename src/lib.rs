@@ -0,0 +1,57 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+#![warn(clippy::all)]
+#![cfg_attr(not(feature = "host"), no_std)]
+
+mod answer;
+mod args;
+mod capacities;
+pub mod day01;
+pub mod day02;
+pub mod day04;
+pub mod day06;
+pub mod day11;
+mod error;
+#[cfg(feature = "results-file")]
+mod hostfile;
+#[cfg(feature = "console-06")]
+mod input;
+#[cfg(feature = "json")]
+mod json;
+pub mod metrics;
+#[cfg(feature = "no-panic")]
+mod no_panic;
+pub mod output;
+mod parse;
+mod runner;
+mod solution;
+pub mod testing;
+pub mod top;
+
+pub use answer::{Answer, ANSWER_TEXT_LEN};
+pub use error::{Error, OrBadInput, OrOverflow};
+pub use runner::{check_precomputed, run, run_precomputed};
+pub use solution::Solution;
+
+#[cfg(feature = "host")]
+pub use aoc22_host_entry::entry;
+#[cfg(not(feature = "host"))]
+pub use cortex_m_rt::entry;
+
+// Panics report through the same channel as everything else.
+#[cfg(all(feature = "uart", not(feature = "no-panic")))]
+use panic_halt as _;
+#[cfg(not(any(feature = "uart", feature = "host", feature = "no-panic")))]
+use panic_semihosting as _;
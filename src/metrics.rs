@@ -0,0 +1,124 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Run-time measurements: elapsed SysTick ticks and stack high-water mark.
+//!
+//! QEMU does not model the DWT cycle counter on the Cortex-M3, so ticks come from
+//! SysTick running off the core clock, with wrap-arounds counted in its exception.
+//! Natively (`host`), ticks are nanoseconds and the stack is not measured.
+
+#[cfg(not(feature = "host"))]
+pub use self::cortex_m::*;
+#[cfg(feature = "host")]
+pub use self::host::*;
+
+#[cfg(not(feature = "host"))]
+mod cortex_m {
+    use core::ptr;
+    use core::sync::atomic::{AtomicU32, Ordering};
+    use cortex_m::peripheral::{syst::SystClkSource, SYST};
+    use cortex_m_rt::exception;
+
+    const RELOAD: u32 = 0x00FF_FFFF;
+    const PAINT: u32 = 0xA5A5_A5A5;
+    // Leave the words just below the stack pointer alone while painting.
+    const PAINT_MARGIN: u32 = 64;
+
+    static WRAPS: AtomicU32 = AtomicU32::new(0);
+
+    extern "C" {
+        // Provided by cortex-m-rt's `link.x`: the end of statics and the top of the stack.
+        static mut __sheap: u32;
+        static _stack_start: u32;
+    }
+
+    #[exception]
+    fn SysTick() {
+        WRAPS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Starts the free-running SysTick counter. Does nothing if it is already taken.
+    pub fn start() {
+        if let Some(mut cp) = cortex_m::Peripherals::take() {
+            cp.SYST.set_clock_source(SystClkSource::Core);
+            cp.SYST.set_reload(RELOAD);
+            cp.SYST.clear_current();
+            cp.SYST.enable_interrupt();
+            cp.SYST.enable_counter();
+        }
+    }
+
+    /// Core clock ticks since `start`.
+    pub fn ticks() -> u64 {
+        loop {
+            let wraps = WRAPS.load(Ordering::Relaxed);
+            let current = SYST::get_current();
+            if WRAPS.load(Ordering::Relaxed) == wraps {
+                return (u64::from(wraps) << 24) | u64::from(RELOAD - current);
+            }
+        }
+    }
+
+    /// Fills the unused part of the stack with a known pattern.
+    pub fn paint_stack() {
+        let limit = cortex_m::register::msp::read() - PAINT_MARGIN;
+        // SAFETY: everything between the end of statics and the current stack pointer
+        // (less a margin) is unused, so it can be overwritten.
+        unsafe {
+            let mut p = ptr::addr_of_mut!(__sheap);
+            while (p as u32) < limit {
+                ptr::write_volatile(p, PAINT);
+                p = p.add(1);
+            }
+        }
+    }
+
+    /// Deepest stack use, in bytes, since the last `paint_stack`.
+    pub fn stack_high_water() -> u32 {
+        // SAFETY: only reads the region painted by `paint_stack`, which lies below the
+        // stack pointer and is never mapped to anything else.
+        unsafe {
+            let top = ptr::addr_of!(_stack_start) as u32;
+            let mut p = ptr::addr_of!(__sheap);
+            while (p as u32) < top && ptr::read_volatile(p) == PAINT {
+                p = p.add(1);
+            }
+            top - p as u32
+        }
+    }
+}
+
+#[cfg(feature = "host")]
+mod host {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static START: OnceLock<Instant> = OnceLock::new();
+
+    pub fn start() {
+        START.get_or_init(Instant::now);
+    }
+
+    /// Nanoseconds since `start`.
+    pub fn ticks() -> u64 {
+        START.get_or_init(Instant::now).elapsed().as_nanos() as u64
+    }
+
+    pub fn paint_stack() {}
+
+    /// Not measured natively; always 0.
+    pub fn stack_high_water() -> u32 {
+        0
+    }
+}
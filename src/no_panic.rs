@@ -0,0 +1,35 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Link-time proof that nothing can panic. The panic handler calls a function
+//! that does not exist, so the link only succeeds if the optimizer removed every
+//! path to it. Build in release mode; debug builds keep overflow and bounds checks
+//! that the optimizer would otherwise have proven unnecessary.
+
+#[cfg(feature = "host")]
+compile_error!("`no-panic` needs this crate's own panic handler, which `std` provides");
+
+use core::panic::PanicInfo;
+
+extern "Rust" {
+    // Deliberately undefined. If the linker reports it missing, some code can
+    // still panic; the build without `no-panic` and a debugger will show where.
+    fn aoc22_nostd_code_can_panic() -> !;
+}
+
+#[panic_handler]
+fn panic(_: &PanicInfo) -> ! {
+    // SAFETY: never actually called; the link fails if it is reachable.
+    unsafe { aoc22_nostd_code_can_panic() }
+}
@@ -0,0 +1,164 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Where reports and diagnostics go. The sink is picked with a cargo feature:
+//! semihosting by default, `uart` for UART0, or `host` for the host's stdout.
+
+use core::fmt;
+
+#[cfg(all(feature = "uart", feature = "host"))]
+compile_error!("features `uart` and `host` select different output sinks; enable only one");
+
+/// A destination for text output.
+pub trait Sink: fmt::Write + Sized {
+    /// Returns a handle to the sink, setting it up on first use.
+    fn open() -> Self;
+}
+
+/// Prints through the debugger (or QEMU) with semihosting. Slow, but needs no hardware.
+#[cfg(not(feature = "host"))]
+pub struct Semihosting;
+
+#[cfg(not(feature = "host"))]
+impl Sink for Semihosting {
+    fn open() -> Self {
+        Semihosting
+    }
+}
+
+#[cfg(not(feature = "host"))]
+impl fmt::Write for Semihosting {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        cortex_m_semihosting::hprint!("{}", s).map_err(|_| fmt::Error)
+    }
+}
+
+/// Transmits on the LM3S6965's UART0 (QEMU's first `-serial`).
+#[cfg(not(feature = "host"))]
+pub struct Uart0;
+
+#[cfg(not(feature = "host"))]
+impl Uart0 {
+    const RCGC1: *mut u32 = 0x400F_E104 as *mut u32;
+    const DR: *mut u32 = 0x4000_C000 as *mut u32;
+    const FR: *const u32 = 0x4000_C018 as *const u32;
+    const CTL: *mut u32 = 0x4000_C030 as *mut u32;
+    const FR_TXFF: u32 = 1 << 5;
+    const CTL_UARTEN_TXE: u32 = (1 << 0) | (1 << 8);
+
+    fn write_byte(b: u8) {
+        // SAFETY: DR and FR are UART0's data and flag registers.
+        unsafe {
+            while core::ptr::read_volatile(Self::FR) & Self::FR_TXFF != 0 {}
+            core::ptr::write_volatile(Self::DR, u32::from(b));
+        }
+    }
+}
+
+#[cfg(not(feature = "host"))]
+impl Sink for Uart0 {
+    fn open() -> Self {
+        // SAFETY: gating on UART0's clock and enabling its transmitter only touches
+        // UART0's own bits. Baud rate and pin muxing keep their reset values, which
+        // is all QEMU needs.
+        unsafe {
+            let rcgc1 = core::ptr::read_volatile(Self::RCGC1);
+            core::ptr::write_volatile(Self::RCGC1, rcgc1 | 1);
+            let ctl = core::ptr::read_volatile(Self::CTL);
+            core::ptr::write_volatile(Self::CTL, ctl | Self::CTL_UARTEN_TXE);
+        }
+        Uart0
+    }
+}
+
+#[cfg(not(feature = "host"))]
+impl fmt::Write for Uart0 {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for b in s.bytes() {
+            if b == b'\n' {
+                Self::write_byte(b'\r');
+            }
+            Self::write_byte(b);
+        }
+        Ok(())
+    }
+}
+
+/// The host's standard output.
+#[cfg(feature = "host")]
+pub struct Stdout;
+
+#[cfg(feature = "host")]
+impl Sink for Stdout {
+    fn open() -> Self {
+        Stdout
+    }
+}
+
+#[cfg(feature = "host")]
+impl fmt::Write for Stdout {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        use std::io::Write;
+        std::io::stdout()
+            .write_all(s.as_bytes())
+            .map_err(|_| fmt::Error)
+    }
+}
+
+/// The sink selected by cargo features.
+#[cfg(not(any(feature = "uart", feature = "host")))]
+pub type Console = Semihosting;
+#[cfg(feature = "uart")]
+pub type Console = Uart0;
+#[cfg(feature = "host")]
+pub type Console = Stdout;
+
+/// Like `println!`, but to the selected `Console`. Evaluates to a `fmt::Result`.
+#[macro_export]
+macro_rules! outputln {
+    ($($arg:tt)*) => {{
+        use core::fmt::Write as _;
+        let mut sink = <$crate::output::Console as $crate::output::Sink>::open();
+        writeln!(sink, $($arg)*)
+    }};
+}
+
+/// Ends the run with a pass or fail status.
+pub fn exit(success: bool) -> ! {
+    #[cfg(feature = "host")]
+    std::process::exit(if success { 0 } else { 1 });
+
+    // Without a debugger attached there is nobody to report the status to.
+    #[cfg(feature = "uart")]
+    {
+        let _ = success;
+        loop {
+            cortex_m::asm::wfi();
+        }
+    }
+
+    #[cfg(not(any(feature = "uart", feature = "host")))]
+    {
+        use cortex_m_semihosting::debug;
+        debug::exit(if success {
+            debug::EXIT_SUCCESS
+        } else {
+            debug::EXIT_FAILURE
+        });
+        // Only reached without a debugger to act on the exit request.
+        loop {
+            cortex_m::asm::wfi();
+        }
+    }
+}
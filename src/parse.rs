@@ -0,0 +1,100 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Parsers shared by the days, for where `nom`'s own would panic.
+//!
+//! `nom`'s number parsers slice their input in ways the optimizer cannot prove in
+//! bounds, so they keep a panic path alive even though they never actually take
+//! it. These are drop-in replacements that the `no-panic` link check accepts.
+
+use nom::{
+    branch::alt,
+    character::complete::{digit1, line_ending},
+    combinator::{eof, map_opt},
+    IResult,
+};
+
+/// An unsigned decimal number; fails if it does not fit in a `u64`.
+pub fn u64(i: &[u8]) -> IResult<&[u8], u64> {
+    map_opt(digit1, |digits: &[u8]| {
+        digits.iter().try_fold(0u64, |n, d| {
+            n.checked_mul(10)?.checked_add(u64::from(d - b'0'))
+        })
+    })(i)
+}
+
+/// An unsigned decimal number; fails if it does not fit in a `u32`.
+pub fn u32(i: &[u8]) -> IResult<&[u8], u32> {
+    map_opt(u64, |n| u32::try_from(n).ok())(i)
+}
+
+/// An unsigned decimal number; fails if it does not fit in a `usize`.
+pub fn usize(i: &[u8]) -> IResult<&[u8], usize> {
+    map_opt(u64, |n| usize::try_from(n).ok())(i)
+}
+
+/// A line ending, or the end of the input, so the last line does not need one.
+pub fn line_end(i: &[u8]) -> IResult<&[u8], &[u8]> {
+    alt((line_ending, eof))(i)
+}
+
+/// Reads the unsigned decimal number at `input[at..]`, for `const fn` parsers, which
+/// cannot use `nom`. Returns it with the index just past it, or `None` if there are
+/// no digits there or the number does not fit in a `u32`.
+pub const fn const_u32(input: &[u8], at: usize) -> Option<(u32, usize)> {
+    let mut n: u32 = 0;
+    let mut i = at;
+    while i < input.len() && input[i].is_ascii_digit() {
+        n = match n.checked_mul(10) {
+            Some(n) => match n.checked_add((input[i] - b'0') as u32) {
+                Some(n) => n,
+                None => return None,
+            },
+            None => return None,
+        };
+        i += 1;
+    }
+    if i == at {
+        None
+    } else {
+        Some((n, i))
+    }
+}
+
+/// Skips the `\n` or `\r\n` at `input[at..]`, for `const fn` parsers. The end of the
+/// input counts as a line ending too. Returns the index after it.
+pub const fn const_line_end(input: &[u8], at: usize) -> Option<usize> {
+    if at == input.len() {
+        Some(at)
+    } else if input[at] == b'\n' {
+        Some(at + 1)
+    } else if input[at] == b'\r' && at + 1 < input.len() && input[at + 1] == b'\n' {
+        Some(at + 2)
+    } else {
+        None
+    }
+}
+
+/// Whether `input[at..]` is only spaces, tabs and line endings, which the runtime
+/// parsers allow after the last line. For `const fn` parsers.
+pub const fn const_only_space(input: &[u8], at: usize) -> bool {
+    let mut i = at;
+    while i < input.len() {
+        if !matches!(input[i], b' ' | b'\t' | b'\r' | b'\n') {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
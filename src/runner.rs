@@ -0,0 +1,352 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::{metrics, output, outputln, Answer, Error, Solution};
+use core::fmt::Write;
+use heapless::String;
+
+#[cfg(feature = "results-file")]
+use crate::hostfile::HostFile;
+#[cfg(feature = "json")]
+use crate::json::JsonObject;
+
+/// Longest result line; enough for the longest text answer or error message.
+const LINE_LEN: usize = 192;
+
+type Line = String<LINE_LEN>;
+
+/// Expected answers for a day, taken from `AOC22_EXPECT_<day>_<part>` at build time.
+///
+/// For example, `AOC22_EXPECT_01_1=24000 cargo run --release --bin day01`.
+#[macro_export]
+macro_rules! expected {
+    ($day:literal) => {
+        [
+            option_env!(concat!("AOC22_EXPECT_", $day, "_1")),
+            option_env!(concat!("AOC22_EXPECT_", $day, "_2")),
+        ]
+    };
+}
+
+/// Runs a day in the `const-eval` mode: `$answers` is a `const fn` call that the
+/// compiler evaluates, so the firmware only reports constants. Expected answers are
+/// checked at compile time too, and a mismatch fails the build.
+///
+/// For example, `run_const!(Day01, day01::answers(INPUT.as_bytes()), expected!("01"))`.
+#[macro_export]
+macro_rules! run_const {
+    ($solution:ty, $answers:expr, $expected:expr) => {{
+        // Real inputs take the const evaluator a while.
+        #[allow(long_running_const_eval)]
+        const ANSWERS: [Result<u64, $crate::Error>; 2] = $answers;
+        const EXPECTED: [Option<&str>; 2] = $expected;
+        const _: () = $crate::check_precomputed(&ANSWERS, EXPECTED);
+        $crate::run_precomputed(<$solution as $crate::Solution>::DAY, ANSWERS, EXPECTED)
+    }};
+}
+
+/// Fails const evaluation, and so the build, if an answer is not the expected one.
+#[doc(hidden)]
+pub const fn check_precomputed(answers: &[Result<u64, Error>; 2], expected: [Option<&str>; 2]) {
+    let mut part = 0;
+    while part < 2 {
+        if let Some(expected) = expected[part] {
+            match answers[part] {
+                Ok(answer) => {
+                    if !matches!(parse_expected(expected.as_bytes()), Some(e) if e == answer) {
+                        panic!("an answer does not match its AOC22_EXPECT_* value");
+                    }
+                }
+                Err(_) => panic!("a part with an AOC22_EXPECT_* value failed"),
+            }
+        }
+        part += 1;
+    }
+}
+
+/// Reads an expected answer as a number, ignoring surrounding whitespace.
+const fn parse_expected(s: &[u8]) -> Option<u64> {
+    let mut n: u64 = 0;
+    let mut digits = 0;
+    let mut i = 0;
+    while i < s.len() {
+        match s[i] {
+            b'0'..=b'9' if digits == 0 || s[i - 1].is_ascii_digit() => {
+                n = match n.checked_mul(10) {
+                    Some(n) => match n.checked_add((s[i] - b'0') as u64) {
+                        Some(n) => n,
+                        None => return None,
+                    },
+                    None => return None,
+                };
+                digits += 1;
+            }
+            b' ' | b'\t' | b'\r' | b'\n' => {}
+            _ => return None,
+        }
+        i += 1;
+    }
+    if digits > 0 {
+        Some(n)
+    } else {
+        None
+    }
+}
+
+/// How one part of a run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    /// The answer matched the expected one.
+    Ok,
+    /// There was no expected answer to check against.
+    Unchecked,
+    /// The answer did not match the expected one.
+    Wrong,
+    /// The solution failed to produce an answer.
+    Error,
+}
+
+impl Status {
+    #[cfg(feature = "json")]
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Unchecked => "unchecked",
+            Status::Wrong => "wrong",
+            Status::Error => "error",
+        }
+    }
+}
+
+/// One part's outcome, with the measurements taken while solving it.
+#[cfg_attr(not(feature = "json"), allow(dead_code))]
+struct PartResult<'a> {
+    day: u8,
+    part: u8,
+    answer: Result<Answer, Error>,
+    expected: Option<&'a str>,
+    ticks: u64,
+    stack: u32,
+}
+
+impl PartResult<'_> {
+    fn status(&self) -> Status {
+        match (&self.answer, self.expected) {
+            (Err(_), _) => Status::Error,
+            (Ok(_), None) => Status::Unchecked,
+            (Ok(answer), Some(want)) if answer.matches(want) => Status::Ok,
+            (Ok(_), Some(_)) => Status::Wrong,
+        }
+    }
+}
+
+/// The measurements taken while parsing a day's input, which both parts share.
+#[cfg_attr(not(feature = "json"), allow(dead_code))]
+struct ParseStep {
+    day: u8,
+    ticks: u64,
+    stack: u32,
+}
+
+/// Runs `f`, returning its result with the ticks and stack bytes it took.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, u64, u32) {
+    metrics::paint_stack();
+    let start = metrics::ticks();
+    let result = f();
+    let ticks = metrics::ticks() - start;
+    (result, ticks, metrics::stack_high_water())
+}
+
+fn solve<'a>(
+    day: u8,
+    part: u8,
+    solver: impl FnOnce() -> Result<Answer, Error>,
+    expected: Option<&'a str>,
+) -> PartResult<'a> {
+    let (answer, ticks, stack) = measure(solver);
+    PartResult {
+        day,
+        part,
+        answer,
+        expected,
+        ticks,
+        stack,
+    }
+}
+
+#[cfg(not(feature = "json"))]
+fn render(r: &PartResult, with_metrics: bool) -> Line {
+    let mut line = Line::new();
+    // A line that does not fit is cut short rather than dropped.
+    let _ = match &r.answer {
+        Err(err) => write!(line, "Error: {}", err),
+        Ok(answer) => match (r.status(), r.expected) {
+            (Status::Wrong, Some(want)) => {
+                write!(
+                    line,
+                    "Part {}: {} (expected {})",
+                    r.part,
+                    answer,
+                    want.trim()
+                )
+            }
+            _ => write!(line, "Part {}: {}", r.part, answer),
+        },
+    };
+    if with_metrics {
+        let _ = write!(line, " [{} ticks, {} bytes of stack]", r.ticks, r.stack);
+    }
+    line
+}
+
+/// The parse step has no answer, so it only shows up where the measurements do.
+#[cfg(not(feature = "json"))]
+fn render_parse(p: &ParseStep, with_metrics: bool) -> Option<Line> {
+    let mut line = Line::new();
+    if with_metrics {
+        let _ = write!(line, "Parse: {} ticks, {} bytes of stack", p.ticks, p.stack);
+        Some(line)
+    } else {
+        None
+    }
+}
+
+#[cfg(feature = "json")]
+fn render_parse(p: &ParseStep, _with_metrics: bool) -> Option<Line> {
+    let mut obj: JsonObject<LINE_LEN> = JsonObject::new();
+    obj.number("day", p.day)
+        .string("step", "parse")
+        .number("ticks", p.ticks)
+        .number("stack", p.stack);
+    let mut line = Line::new();
+    line.push_str(obj.finish().ok()?).ok()?;
+    Some(line)
+}
+
+#[cfg(feature = "json")]
+fn render(r: &PartResult, _with_metrics: bool) -> Line {
+    let mut obj: JsonObject<LINE_LEN> = JsonObject::new();
+    obj.number("day", r.day).number("part", r.part);
+    match &r.answer {
+        Ok(Answer::Text(text)) => obj.string("answer", text),
+        Ok(answer) => obj.number("answer", answer),
+        Err(_) => obj.null("answer"),
+    };
+    obj.number("ticks", r.ticks)
+        .number("stack", r.stack)
+        .string("status", r.status().as_str());
+    if let Err(err) = &r.answer {
+        obj.display("error", err);
+    }
+    let mut line = Line::new();
+    let _ = match obj.finish() {
+        Ok(json) => line.push_str(json),
+        Err(_) => write!(
+            line,
+            "{{\"day\":{},\"part\":{},\"status\":\"error\",\"error\":\"result line too long\"}}",
+            r.day, r.part
+        )
+        .map_err(|_| ()),
+    };
+    line
+}
+
+/// Opens `results/NN.txt` on the host, warning on the console if that fails.
+#[cfg(feature = "results-file")]
+fn results_file(day: u8) -> Option<HostFile> {
+    let mut path: String<32> = String::new();
+    write!(path, "results/{:02}.txt", day).ok()?;
+    let file = HostFile::create(&path).ok();
+    if file.is_none() {
+        outputln!("Warning: could not create {}", path).ok();
+    }
+    file
+}
+
+/// Solves both parts of a day, reports the answers and exits.
+pub fn run<S: Solution>(input: &str, expected: [Option<&str>; 2]) -> ! {
+    metrics::start();
+    // A parse error fails both parts.
+    let (parsed, ticks, stack) = measure(|| S::parse(input));
+    let parse = ParseStep {
+        day: S::DAY,
+        ticks,
+        stack,
+    };
+
+    let results = [
+        solve(
+            S::DAY,
+            1,
+            || S::part1(parsed.as_ref().map_err(|e| *e)?),
+            expected[0],
+        ),
+        solve(
+            S::DAY,
+            2,
+            || S::part2(parsed.as_ref().map_err(|e| *e)?),
+            expected[1],
+        ),
+    ];
+    report(S::DAY, Some(&parse), &results)
+}
+
+/// Reports answers that were worked out at compile time and exits. Use it through
+/// `run_const!`. The measurements show what is left to do at run time, which does
+/// not include any parsing.
+pub fn run_precomputed(
+    day: u8,
+    answers: [Result<u64, Error>; 2],
+    expected: [Option<&str>; 2],
+) -> ! {
+    metrics::start();
+    let results = [
+        solve(day, 1, || answers[0].map(Answer::from), expected[0]),
+        solve(day, 2, || answers[1].map(Answer::from), expected[1]),
+    ];
+    report(day, None, &results)
+}
+
+/// Prints the results (and saves them, with `results-file`), then exits.
+fn report(day: u8, parse: Option<&ParseStep>, results: &[PartResult; 2]) -> ! {
+    if let Some(line) = parse.and_then(|p| render_parse(p, false)) {
+        outputln!("{}", line).ok();
+    }
+    for r in results.iter() {
+        outputln!("{}", render(r, false)).ok();
+    }
+
+    #[cfg(feature = "results-file")]
+    if let Some(mut file) = results_file(day) {
+        let parse = parse.and_then(|p| render_parse(p, true));
+        let lines = parse
+            .into_iter()
+            .chain(results.iter().map(|r| render(r, true)));
+        for line in lines {
+            if writeln!(file, "{}", line).is_err() {
+                outputln!("Warning: could not write results/{:02}.txt", day).ok();
+                break;
+            }
+        }
+    }
+    #[cfg(not(feature = "results-file"))]
+    let _ = day;
+
+    // Exit QEMU.
+    output::exit(
+        results
+            .iter()
+            .all(|r| matches!(r.status(), Status::Ok | Status::Unchecked)),
+    )
+}
@@ -0,0 +1,29 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use crate::{Answer, Error};
+
+/// One day's puzzle: a parser plus the two parts.
+pub trait Solution {
+    /// Day of the month, used for reporting.
+    const DAY: u8;
+
+    /// The input after parsing, shared by both parts. Days that parse as they go
+    /// can just keep the raw `&str`.
+    type Parsed<'a>;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>, Error>;
+    fn part1(parsed: &Self::Parsed<'_>) -> Result<Answer, Error>;
+    fn part2(parsed: &Self::Parsed<'_>) -> Result<Answer, Error>;
+}
@@ -0,0 +1,109 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! A minimal test framework that runs on the target itself, under QEMU, so the
+//! tests see the real codegen and memory limits. Test functions return
+//! `Result<(), Failed>` and use `check!` and `check_eq!` instead of `assert!`, so
+//! one failure does not end the run. `target_tests!` defines an `#[entry]` that
+//! runs them and exits with the overall status.
+
+use crate::output;
+use crate::outputln;
+
+/// Marks a failed test. The failing check has already printed the details.
+#[derive(Debug)]
+pub struct Failed;
+
+/// A registered test function.
+pub struct Test {
+    pub name: &'static str,
+    pub run: fn() -> Result<(), Failed>,
+}
+
+/// Runs every test, reports each one and a summary, and exits with the result.
+pub fn run(tests: &[Test]) -> ! {
+    let _ = outputln!("running {} tests", tests.len());
+    let mut failed = 0;
+    for test in tests {
+        let result = (test.run)();
+        if result.is_err() {
+            failed += 1;
+        }
+        let _ = outputln!(
+            "test {} ... {}",
+            test.name,
+            if result.is_ok() { "ok" } else { "FAILED" }
+        );
+    }
+    let _ = outputln!(
+        "test result: {}. {} passed; {} failed",
+        if failed == 0 { "ok" } else { "FAILED" },
+        tests.len() - failed,
+        failed
+    );
+    output::exit(failed == 0)
+}
+
+/// Fails the current test if the condition is false.
+#[macro_export]
+macro_rules! check {
+    ($cond:expr) => {
+        if !$cond {
+            let _ = $crate::outputln!(
+                "{}:{}: check failed: {}",
+                file!(),
+                line!(),
+                stringify!($cond)
+            );
+            return Err($crate::testing::Failed);
+        }
+    };
+}
+
+/// Fails the current test if the two values differ, printing both.
+#[macro_export]
+macro_rules! check_eq {
+    ($left:expr, $right:expr) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                if left != right {
+                    let _ = $crate::outputln!(
+                        "{}:{}: check failed: {} == {}\n   left: {:?}\n  right: {:?}",
+                        file!(),
+                        line!(),
+                        stringify!($left),
+                        stringify!($right),
+                        left,
+                        right
+                    );
+                    return Err($crate::testing::Failed);
+                }
+            }
+        }
+    };
+}
+
+/// Defines the `#[entry]` of a test binary that runs the listed test functions.
+#[macro_export]
+macro_rules! target_tests {
+    ($($test:path),* $(,)?) => {
+        #[$crate::entry]
+        fn main() -> ! {
+            $crate::testing::run(&[$($crate::testing::Test {
+                name: stringify!($test),
+                run: $test,
+            }),*])
+        }
+    };
+}
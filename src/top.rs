@@ -0,0 +1,100 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Keeping the largest few values seen, such as day 1's elves and day 11's monkeys.
+//!
+//! It is all `const fn`, so the `const-eval` mode can use it too.
+
+/// The `K` largest values pushed so far, largest first, each with the index of
+/// whatever produced it. Of equal values, the one pushed first ranks higher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TopK<const K: usize> {
+    entries: [(u64, usize); K],
+    len: usize,
+}
+
+impl<const K: usize> TopK<K> {
+    pub const fn new() -> Self {
+        TopK {
+            entries: [(0, 0); K],
+            len: 0,
+        }
+    }
+
+    /// Offers `value`, which came from `index`.
+    pub const fn push(&mut self, value: u64, index: usize) {
+        // Never above `K`, but the optimizer cannot tell.
+        let len = if self.len < K { self.len } else { K };
+        // Below every entry at least as large, so ties keep their order.
+        let mut slot = len;
+        while slot > 0 && self.entries[slot - 1].0 < value {
+            slot -= 1;
+        }
+        if slot >= K {
+            return;
+        }
+        // Shift the smaller ones down, dropping the smallest if full.
+        let mut i = if len < K { len } else { K - 1 };
+        while i > slot {
+            self.entries[i] = self.entries[i - 1];
+            i -= 1;
+        }
+        self.entries[slot] = (value, index);
+        if len < K {
+            self.len = len + 1;
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The `i`th largest value and its index, counting from 0.
+    pub const fn get(&self, i: usize) -> Option<(u64, usize)> {
+        if i < self.len && i < K {
+            Some(self.entries[i])
+        } else {
+            None
+        }
+    }
+
+    /// The values and their indices, largest first.
+    pub fn entries(&self) -> &[(u64, usize)] {
+        self.entries.get(..self.len).unwrap_or(&[])
+    }
+
+    /// The sum of the values, or `None` if it overflows.
+    pub const fn sum(&self) -> Option<u64> {
+        let mut sum: u64 = 0;
+        let mut i = 0;
+        while i < self.len && i < K {
+            sum = match sum.checked_add(self.entries[i].0) {
+                Some(sum) => sum,
+                None => return None,
+            };
+            i += 1;
+        }
+        Some(sum)
+    }
+}
+
+impl<const K: usize> Default for TopK<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
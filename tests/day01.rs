@@ -0,0 +1,85 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Differential tests: day01 against a straightforward `Vec`-based solver.
+
+#![cfg(feature = "host")]
+
+use aoc22_nostd::day01;
+use proptest::prelude::*;
+
+fn reference(elves: &[Vec<u32>]) -> (u32, u32) {
+    let mut totals: Vec<u32> = elves.iter().map(|elf| elf.iter().sum()).collect();
+    totals.sort_unstable_by(|a, b| b.cmp(a));
+    (totals[0], totals.iter().take(3).sum())
+}
+
+fn render(elves: &[Vec<u32>]) -> String {
+    let lines: Vec<String> = elves
+        .iter()
+        .map(|elf| elf.iter().map(|c| format!("{}\n", c)).collect())
+        .collect();
+    lines.join("\n")
+}
+
+/// Count, top three (total, index), least, median and 90th percentile, by sorting.
+fn reference_stats(elves: &[Vec<u32>]) -> (usize, Vec<(u64, usize)>, u32, u32, u32) {
+    let totals: Vec<u32> = elves.iter().map(|elf| elf.iter().sum()).collect();
+    let mut ranked: Vec<(u64, usize)> = totals.iter().map(|&t| t.into()).zip(0..).collect();
+    // Largest first; among equal totals, the earlier elf first.
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    ranked.truncate(3);
+    let mut sorted = totals.clone();
+    sorted.sort_unstable();
+    let rank = |percent: usize| sorted[(sorted.len() * percent).div_ceil(100).max(1) - 1];
+    (totals.len(), ranked, sorted[0], rank(50), rank(90))
+}
+
+proptest! {
+    #[test]
+    fn matches_reference(
+        elves in prop::collection::vec(prop::collection::vec(1u32..100_000, 1..10), 1..50),
+        trailing_newline in any::<bool>()
+    ) {
+        let mut input = render(&elves);
+        if !trailing_newline {
+            input.pop();
+        }
+        let (p1, p2) = reference(&elves);
+        prop_assert_eq!(day01::part1(&input), Ok(p1));
+        prop_assert_eq!(day01::part2(&input), Ok(p2));
+        prop_assert_eq!(day01::answers(input.as_bytes()), [Ok(p1.into()), Ok(p2.into())]);
+    }
+
+    /// The `const fn` reads exactly what the runtime parser reads, and fails the same way.
+    #[test]
+    fn answers_agree_with_parts(input in r"([0-9]{1,11}(\r?\n){0,3}| |x){0,12}") {
+        let parts = [
+            day01::part1(&input).map(u64::from),
+            day01::part2(&input).map(u64::from),
+        ];
+        prop_assert_eq!(day01::answers(input.as_bytes()), parts);
+    }
+
+    #[test]
+    fn stats_match_reference(
+        elves in prop::collection::vec(prop::collection::vec(1u32..1000, 1..3), 1..300)
+    ) {
+        let stats = day01::stats(&render(&elves)).unwrap();
+        let (count, top, min, median, p90) = reference_stats(&elves);
+        prop_assert_eq!(stats.elves, count);
+        prop_assert_eq!(stats.top.entries(), &top[..]);
+        prop_assert_eq!((stats.min, stats.median, stats.p90), (min, median, p90));
+    }
+}
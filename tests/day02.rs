@@ -0,0 +1,164 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Differential tests: day02's rule tables against arithmetic modulo 3, and against
+//! the rules of Rock-Paper-Scissors-Lizard-Spock as they are usually told.
+
+#![cfg(feature = "host")]
+
+use aoc22_nostd::day02;
+use proptest::prelude::*;
+
+// Shapes and outcomes are 0, 1, 2 (rock/paper/scissors, lose/draw/win).
+fn round_score(them: i32, us: i32) -> u32 {
+    let outcome = (us - them + 1).rem_euclid(3);
+    (us + 1 + 3 * outcome) as u32
+}
+
+fn reference(rounds: &[(i32, i32)]) -> (u32, u32) {
+    let p1 = rounds.iter().map(|&(them, us)| round_score(them, us)).sum();
+    let p2 = rounds
+        .iter()
+        .map(|&(them, outcome)| round_score(them, (them + outcome - 1).rem_euclid(3)))
+        .sum();
+    (p1, p2)
+}
+
+fn render(rounds: &[(i32, i32)]) -> String {
+    rounds
+        .iter()
+        .map(|&(them, us)| {
+            format!(
+                "{} {}\n",
+                (b'A' + them as u8) as char,
+                (b'X' + us as u8) as char
+            )
+        })
+        .collect()
+}
+
+const ROCK: usize = 0;
+const SPOCK: usize = 1;
+const PAPER: usize = 2;
+const LIZARD: usize = 3;
+const SCISSORS: usize = 4;
+
+// Who beats whom, one line of the rhyme at a time.
+const BEATS: [(usize, usize); 10] = [
+    (SCISSORS, PAPER),
+    (PAPER, ROCK),
+    (ROCK, LIZARD),
+    (LIZARD, SPOCK),
+    (SPOCK, SCISSORS),
+    (SCISSORS, LIZARD),
+    (LIZARD, PAPER),
+    (PAPER, SPOCK),
+    (SPOCK, ROCK),
+    (ROCK, SCISSORS),
+];
+
+/// Outcome points (0, 3 or 6) of `us` against `them`.
+fn rpsls_outcome(them: usize, us: usize) -> u32 {
+    if BEATS.contains(&(us, them)) {
+        6
+    } else if BEATS.contains(&(them, us)) {
+        0
+    } else {
+        3
+    }
+}
+
+fn rpsls_score(them: usize, us: usize) -> u32 {
+    us as u32 + 1 + rpsls_outcome(them, us)
+}
+
+/// Part 2's total, where the second column is lose/draw/win and the shape played is
+/// the neighbour in cycle order (rock, Spock, paper, lizard, scissors) that gets it.
+fn rpsls_part2(rounds: &[(usize, usize)]) -> u32 {
+    rounds
+        .iter()
+        .map(|&(them, wanted)| {
+            let us = (them + 4 + wanted) % 5;
+            assert_eq!(rpsls_outcome(them, us), 3 * wanted as u32);
+            rpsls_score(them, us)
+        })
+        .sum()
+}
+
+/// A guide with their shapes from `A` and the second column from `second`.
+fn render_rpsls(rounds: &[(usize, usize)], second: u8) -> String {
+    rounds
+        .iter()
+        .map(|&(them, col)| {
+            format!(
+                "{} {}\n",
+                (b'A' + them as u8) as char,
+                (second + col as u8) as char
+            )
+        })
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn matches_reference(
+        rounds in prop::collection::vec((0..3, 0..3), 1..200),
+        trailing_newline in any::<bool>()
+    ) {
+        let mut input = render(&rounds);
+        if !trailing_newline {
+            input.pop();
+        }
+        let (p1, p2) = reference(&rounds);
+        prop_assert_eq!(day02::part1(&input), Ok(p1));
+        prop_assert_eq!(day02::part2(&input), Ok(p2));
+        prop_assert_eq!(day02::answers(input.as_bytes()), [Ok(p1.into()), Ok(p2.into())]);
+    }
+
+    /// The `const fn` reads exactly what the runtime parser reads, and fails the same way.
+    #[test]
+    fn answers_agree_with_parts(input in r"([A-D][ \t]{0,2}[W-Z](\r?\n)?|[ \t\r\n]){0,12}") {
+        let parts = [
+            day02::part1(&input).map(u64::from),
+            day02::part2(&input).map(u64::from),
+        ];
+        prop_assert_eq!(day02::answers(input.as_bytes()), parts);
+    }
+
+    #[test]
+    fn rpsls_matches_reference(rounds in prop::collection::vec((0..5usize, 0..5usize), 1..200)) {
+        let rules = day02::ROCK_PAPER_SCISSORS_LIZARD_SPOCK;
+        let part1 = render_rpsls(&rounds, b'V');
+        // Part 2 only has three letters for its second column.
+        let wanted: Vec<_> = rounds.iter().map(|&(them, us)| (them, us % 3)).collect();
+        let part2 = render_rpsls(&wanted, b'X');
+        let p1 = rounds.iter().map(|&(them, us)| rpsls_score(them, us)).sum();
+        let p2 = rpsls_part2(&wanted);
+        prop_assert_eq!(rules.part1(&part1), Ok(p1));
+        prop_assert_eq!(rules.part2(&part2), Ok(p2));
+        prop_assert_eq!(rules.answers(part1.as_bytes())[0], Ok(p1.into()));
+        prop_assert_eq!(rules.answers(part2.as_bytes())[1], Ok(p2.into()));
+    }
+
+    /// With five shapes, a second-column letter can suit one part and not the other.
+    #[test]
+    fn rpsls_answers_agree_with_parts(input in r"([A-F][ \t]{0,2}[U-Z](\r?\n)?|[ \t\r\n]){0,12}") {
+        let rules = day02::ROCK_PAPER_SCISSORS_LIZARD_SPOCK;
+        let parts = [
+            rules.part1(&input).map(u64::from),
+            rules.part2(&input).map(u64::from),
+        ];
+        prop_assert_eq!(rules.answers(input.as_bytes()), parts);
+    }
+}
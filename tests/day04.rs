@@ -0,0 +1,86 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Differential tests: day04 against the textbook interval predicates.
+
+#![cfg(feature = "host")]
+
+use aoc22_nostd::day04;
+use proptest::prelude::*;
+
+type Range = (u32, u32);
+
+fn reference(pairs: &[(Range, Range)]) -> (u32, u32) {
+    let contains = |a: Range, b: Range| a.0 <= b.0 && b.1 <= a.1;
+    let overlaps = |a: Range, b: Range| a.0 <= b.1 && b.0 <= a.1;
+    let p1 = pairs
+        .iter()
+        .filter(|&&(a, b)| contains(a, b) || contains(b, a))
+        .count();
+    let p2 = pairs.iter().filter(|&&(a, b)| overlaps(a, b)).count();
+    (p1 as u32, p2 as u32)
+}
+
+fn render(pairs: &[(Range, Range)]) -> String {
+    pairs
+        .iter()
+        .map(|(a, b)| format!("{}-{},{}-{}\n", a.0, a.1, b.0, b.1))
+        .collect()
+}
+
+fn range(max: u32) -> impl Strategy<Value = Range> {
+    (0..=max, 0..=max).prop_map(|(x, y)| (x.min(y), x.max(y)))
+}
+
+proptest! {
+    #[test]
+    fn matches_reference_small(
+        pairs in prop::collection::vec((range(20), range(20)), 1..100),
+        trailing_newline in any::<bool>()
+    ) {
+        let mut input = render(&pairs);
+        if !trailing_newline {
+            input.pop();
+        }
+        let (p1, p2) = reference(&pairs);
+        prop_assert_eq!(day04::part1(&input), Ok(p1));
+        prop_assert_eq!(day04::part2(&input), Ok(p2));
+        prop_assert_eq!(day04::answers(input.as_bytes()), [Ok(p1.into()), Ok(p2.into())]);
+    }
+
+    #[test]
+    fn matches_reference_full_range(
+        pairs in prop::collection::vec((range(u32::MAX), range(u32::MAX)), 1..100),
+        trailing_newline in any::<bool>()
+    ) {
+        let mut input = render(&pairs);
+        if !trailing_newline {
+            input.pop();
+        }
+        let (p1, p2) = reference(&pairs);
+        prop_assert_eq!(day04::part1(&input), Ok(p1));
+        prop_assert_eq!(day04::part2(&input), Ok(p2));
+        prop_assert_eq!(day04::answers(input.as_bytes()), [Ok(p1.into()), Ok(p2.into())]);
+    }
+
+    /// The `const fn` reads exactly what the runtime parser reads, and fails the same way.
+    #[test]
+    fn answers_agree_with_parts(input in r"([0-9]{1,2}-[0-9]{1,2},[0-9]{1,2}-[0-9]{1,2}(\r?\n)?|[ ,\r\n-]){0,12}") {
+        let parts = [
+            day04::part1(&input).map(u64::from),
+            day04::part2(&input).map(u64::from),
+        ];
+        prop_assert_eq!(day04::answers(input.as_bytes()), parts);
+    }
+}
@@ -0,0 +1,61 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Differential tests: day06 against a `HashSet` window scan.
+
+#![cfg(feature = "host")]
+
+use aoc22_nostd::day06;
+use proptest::prelude::*;
+use std::collections::HashSet;
+
+/// Where every marker of `n` different bytes ends.
+fn reference(stream: &[u8], n: usize) -> Vec<usize> {
+    stream
+        .windows(n)
+        .enumerate()
+        .filter(|(_, w)| w.iter().collect::<HashSet<_>>().len() == n)
+        .map(|(i, _)| i + n)
+        .collect()
+}
+
+// A stream over a few letters (so repeats are common) with a 14-letter marker
+// spliced in somewhere, like a real puzzle input.
+fn stream() -> impl Strategy<Value = Vec<u8>> {
+    (
+        prop::collection::vec(b'a'..b'g', 0..4000),
+        Just((b'a'..=b'z').collect::<Vec<u8>>()).prop_shuffle(),
+        any::<prop::sample::Index>(),
+    )
+        .prop_map(|(mut background, letters, at)| {
+            let at = at.index(background.len() + 1);
+            background.splice(at..at, letters[..14].iter().copied());
+            background
+        })
+}
+
+proptest! {
+    #[test]
+    fn matches_reference(stream in stream()) {
+        let (all4, all14) = (reference(&stream, 4), reference(&stream, 14));
+        let bytes = || stream.iter().copied();
+        prop_assert_eq!(day06::find_marker::<4>(&stream), all4.first().copied());
+        prop_assert_eq!(day06::find_marker::<14>(&stream), all14.first().copied());
+        prop_assert_eq!(day06::markers::<4>(bytes()).collect::<Vec<_>>(), all4.clone());
+        prop_assert_eq!(day06::markers::<14>(bytes()).collect::<Vec<_>>(), all14.clone());
+        prop_assert_eq!(day06::scan(bytes()), [all4.first().copied(), all14.first().copied()]);
+        let first = |all: &[usize]| Ok(all.first().map_or(0, |&end| end as u64));
+        prop_assert_eq!(day06::answers(&stream), [first(&all4), first(&all14)]);
+    }
+}
@@ -0,0 +1,227 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Differential tests: day11 against a `VecDeque`-per-monkey simulation.
+
+#![cfg(feature = "host")]
+
+use aoc22_nostd::day11::{self, NUM_MONKEYS};
+use aoc22_nostd::Error;
+use proptest::prelude::*;
+use std::collections::VecDeque;
+
+const PRIMES: [u64; 9] = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+
+/// `new = left op right`, where `None` stands for `old`.
+#[derive(Debug, Clone)]
+struct Op {
+    left: Option<u64>,
+    op: char,
+    right: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+struct Monkey {
+    items: Vec<u64>,
+    op: Op,
+    modulus: u64,
+    if_true: usize,
+    if_false: usize,
+}
+
+/// Monkey business after `rounds`, or `None` if a worry level overflows a `u64`,
+/// goes below zero or is divided by zero. Part 2 only reduces worry levels, so it
+/// gives `None` for subtraction and division, which that loses track of.
+fn reference(monkeys: &[Monkey], rounds: usize, part2: bool) -> Option<u64> {
+    if part2 && monkeys.iter().any(|m| matches!(m.op.op, '-' | '/')) {
+        return None;
+    }
+    let product: u64 = monkeys.iter().map(|m| m.modulus).product();
+    let mut queues: Vec<VecDeque<u64>> = monkeys
+        .iter()
+        .map(|m| m.items.iter().copied().collect())
+        .collect();
+    let mut inspections = vec![0u64; monkeys.len()];
+    for _ in 0..rounds {
+        for (i, m) in monkeys.iter().enumerate() {
+            while let Some(old) = queues[i].pop_front() {
+                inspections[i] += 1;
+                let term = |t: Option<u64>| u128::from(t.unwrap_or(old));
+                let (a, b) = (term(m.op.left), term(m.op.right));
+                let new = match m.op.op {
+                    '+' => a + b,
+                    '-' => a.checked_sub(b)?,
+                    '/' => a.checked_div(b)?,
+                    _ => a * b,
+                };
+                let new = if part2 {
+                    (new % u128::from(product)) as u64
+                } else {
+                    u64::try_from(new).ok()? / 3
+                };
+                let target = if new % m.modulus == 0 {
+                    m.if_true
+                } else {
+                    m.if_false
+                };
+                queues[target].push_back(new);
+            }
+        }
+    }
+    inspections.sort_unstable();
+    Some(inspections[inspections.len() - 1] * inspections[inspections.len() - 2])
+}
+
+fn render(monkeys: &[Monkey]) -> String {
+    let blocks: Vec<String> = monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let items: Vec<String> = m.items.iter().map(|x| x.to_string()).collect();
+            let term = |t: Option<u64>| t.map_or("old".to_string(), |n| n.to_string());
+            let op = format!("{} {} {}", term(m.op.left), m.op.op, term(m.op.right));
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                i, items.join(", "), op, m.modulus, m.if_true, m.if_false
+            )
+        })
+        .collect();
+    blocks.join("\n")
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    let term = || prop::option::weighted(0.5, 1u64..20);
+    let operator = prop_oneof![3 => Just('+'), 3 => Just('*'), 1 => Just('-'), 1 => Just('/')];
+    (term(), operator, term()).prop_map(|(left, op, right)| Op { left, op, right })
+}
+
+// Up to `NUM_MONKEYS` monkeys with distinct prime moduli.
+fn monkeys() -> impl Strategy<Value = Vec<Monkey>> {
+    (2..=NUM_MONKEYS)
+        .prop_flat_map(|n| {
+            let monkey = (prop::collection::vec(0u64..1000, 0..8), op(), 1..n, 1..n);
+            (
+                prop::collection::vec(monkey, n),
+                Just(PRIMES.to_vec()).prop_shuffle(),
+            )
+        })
+        .prop_map(|(monkeys, primes)| {
+            let n = monkeys.len();
+            monkeys
+                .into_iter()
+                .zip(primes)
+                .enumerate()
+                .map(|(i, ((items, op, t, f), modulus))| Monkey {
+                    items,
+                    op,
+                    modulus,
+                    // Never throw to yourself.
+                    if_true: (i + t) % n,
+                    if_false: (i + f) % n,
+                })
+                .collect()
+        })
+}
+
+#[test]
+fn too_many_monkeys_reports_how_many() {
+    let n = NUM_MONKEYS + 3;
+    let monkeys: Vec<Monkey> = (0..n)
+        .map(|i| Monkey {
+            items: vec![1],
+            op: Op {
+                left: None,
+                op: '+',
+                right: Some(1),
+            },
+            modulus: 2,
+            if_true: (i + 1) % n,
+            if_false: (i + 1) % n,
+        })
+        .collect();
+    assert_eq!(
+        day11::parse(render(&monkeys).as_bytes()).unwrap_err(),
+        Error::Capacity {
+            day: 11,
+            buffer: "DAY11_NUM_MONKEYS",
+            capacity: NUM_MONKEYS,
+            needed: n,
+        }
+    );
+}
+
+#[test]
+fn part2_works_with_a_mod_product_above_u32() {
+    // The remainders go above 2^32, so squaring one needs more than a `u64`.
+    let monkeys = vec![
+        Monkey {
+            items: vec![4_294_967_310, 7],
+            op: Op {
+                left: None,
+                op: '*',
+                right: None,
+            },
+            modulus: 4_294_967_311,
+            if_true: 1,
+            if_false: 1,
+        },
+        Monkey {
+            items: vec![2],
+            op: Op {
+                left: None,
+                op: '+',
+                right: Some(1),
+            },
+            modulus: 3,
+            if_true: 0,
+            if_false: 0,
+        },
+    ];
+    let parsed = day11::parse(render(&monkeys).as_bytes()).unwrap();
+    let want = reference(&monkeys, 10_000, true).unwrap();
+    assert_eq!(day11::part2(&parsed), Ok(want));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(32))]
+
+    #[test]
+    fn matches_reference(monkeys in monkeys(), crlf in any::<bool>()) {
+        let mut input = render(&monkeys);
+        if crlf {
+            input = input.replace('\n', "\r\n");
+        }
+        let parsed = day11::parse(input.as_bytes()).unwrap();
+        for (part, rounds, result) in [
+            (1, 20, day11::part1(&parsed)),
+            (2, 10_000, day11::part2(&parsed)),
+        ] {
+            match reference(&monkeys, rounds, part == 2) {
+                Some(want) => prop_assert_eq!(result, Ok(want), "part {}", part),
+                None => prop_assert!(result.is_err(), "part {} should fail", part),
+            }
+        }
+        if monkeys.iter().any(|m| matches!(m.op.op, '-' | '/')) {
+            let unsupported = matches!(
+                day11::part2(&parsed),
+                Err(Error::Unsupported { day: 11, part: 2, .. })
+            );
+            prop_assert!(unsupported, "part 2 should reject - and /");
+        }
+        prop_assert_eq!(
+            day11::part2_cycles(&parsed, 10_000),
+            day11::part2(&parsed).map(u128::from)
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//  Copyright 2022 Google LLC
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      https://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+//! Tests that run on the emulated Cortex-M3 (`cargo test --test target`), or
+//! natively with `--features host`. See `aoc22_nostd::testing`.
+
+#![warn(clippy::all)]
+#![no_std]
+#![no_main]
+
+use aoc22_nostd::testing::Failed;
+use aoc22_nostd::top::TopK;
+use aoc22_nostd::{check, check_eq, day01, day02, day04, day06, day11, target_tests, Error};
+
+const DAY01_SAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+const DAY02_SAMPLE: &str = "A Y\nB X\nC Z\n";
+const DAY04_SAMPLE: &str = "2-4,6-8\n2-3,4-5\n5-7,7-9\n2-8,3-7\n6-6,4-6\n2-6,4-8\n";
+const DAY06_SAMPLES: [(&str, usize, usize); 5] = [
+    ("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 7, 19),
+    ("bvwbjplbgvbhsrlpgdmjqwftvncz", 5, 23),
+    ("nppdvjthqldpwncqszvftbjmhlfdtqgfz", 6, 23),
+    ("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 10, 29),
+    ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11, 26),
+];
+// Part 2 needs 64-bit multiplication and remainders, which the Cortex-M3 does in
+// software.
+const DAY11_SAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+fn day01_sample() -> Result<(), Failed> {
+    check_eq!(day01::part1(DAY01_SAMPLE), Ok(24000));
+    check_eq!(day01::part2(DAY01_SAMPLE), Ok(45000));
+    Ok(())
+}
+
+fn day01_sample_stats() -> Result<(), Failed> {
+    let Ok(stats) = day01::stats(DAY01_SAMPLE) else {
+        return Err(Failed);
+    };
+    check_eq!(stats.elves, 5);
+    check_eq!(
+        stats.top.entries(),
+        &[(24000, 3), (11000, 2), (10000, 4)][..]
+    );
+    check_eq!((stats.min, stats.median, stats.p90), (4000, 10000, 24000));
+    // An overflow in the statistics is not blamed on either part.
+    check!(matches!(
+        day01::stats("4294967295\n1\n"),
+        Err(Error::Overflow {
+            day: 1,
+            part: 0,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+fn day02_sample() -> Result<(), Failed> {
+    check_eq!(day02::part1(DAY02_SAMPLE), Ok(15));
+    check_eq!(day02::part2(DAY02_SAMPLE), Ok(12));
+    Ok(())
+}
+
+fn day02_rock_paper_scissors_lizard_spock() -> Result<(), Failed> {
+    let rules = day02::ROCK_PAPER_SCISSORS_LIZARD_SPOCK;
+    // Our lizard loses to rock, our paper loses to scissors, our scissors beat
+    // paper; then a draw with rock, a loss to scissors (lizard) and a win over
+    // paper (lizard).
+    let guide = "A Y\nE X\nC Z\n";
+    check_eq!(rules.part1(guide), Ok(18));
+    check_eq!(rules.part2(guide), Ok(18));
+    check_eq!(rules.answers(guide.as_bytes()), [Ok(18), Ok(18)]);
+    Ok(())
+}
+
+fn day04_sample() -> Result<(), Failed> {
+    check_eq!(day04::part1(DAY04_SAMPLE), Ok(2));
+    check_eq!(day04::part2(DAY04_SAMPLE), Ok(4));
+    Ok(())
+}
+
+fn malformed_lines_are_errors() -> Result<(), Failed> {
+    // A bad line fails the whole input rather than ending it with a partial total.
+    check!(matches!(
+        day01::part1("1\n\nx\n5\n"),
+        Err(Error::Parse { day: 1, .. })
+    ));
+    check!(matches!(
+        day02::part1("A Y\nB Q\nC Z\n"),
+        Err(Error::Parse { day: 2, .. })
+    ));
+    check!(matches!(
+        day04::part2("2-4,6-8\n2-3\n"),
+        Err(Error::Parse { day: 4, .. })
+    ));
+    // The last line does not need a line ending.
+    check_eq!(day01::part1("1\n\n5"), Ok(5));
+    // The `const fn`s read the same grammar.
+    check_eq!(day01::answers(b"1\n\n5"), [Ok(5), Ok(6)]);
+    check!(matches!(
+        day02::answers(b"A Y\nB Q\nC Z\n"),
+        [Err(Error::Parse { day: 2, .. }), _]
+    ));
+    Ok(())
+}
+
+fn day06_samples() -> Result<(), Failed> {
+    for (stream, first, second) in DAY06_SAMPLES {
+        check_eq!(day06::find_marker::<4>(stream.as_bytes()), Some(first));
+        check_eq!(day06::find_marker::<14>(stream.as_bytes()), Some(second));
+    }
+    Ok(())
+}
+
+fn day06_streams_any_length() -> Result<(), Failed> {
+    // Far longer than any buffer, with both markers right at the end.
+    let stream =
+        core::iter::repeat_n(b'a', 100_000).chain(b"bcdefghijklmn\nopqrstuvwxyz".iter().copied());
+    check_eq!(day06::scan(stream), [Some(100_003), Some(100_013)]);
+    // Nothing past the end of the line is part of the stream.
+    check_eq!(day06::scan(*b"abab\r\ncd"), [None, None]);
+    Ok(())
+}
+
+fn const_answers_match() -> Result<(), Failed> {
+    check_eq!(
+        day01::answers(DAY01_SAMPLE.as_bytes()),
+        [Ok(24000), Ok(45000)]
+    );
+    check_eq!(day02::answers(DAY02_SAMPLE.as_bytes()), [Ok(15), Ok(12)]);
+    check_eq!(day04::answers(DAY04_SAMPLE.as_bytes()), [Ok(2), Ok(4)]);
+    for (stream, first, second) in DAY06_SAMPLES {
+        check_eq!(
+            day06::answers(stream.as_bytes()),
+            [Ok(first as u64), Ok(second as u64)]
+        );
+    }
+    Ok(())
+}
+
+fn day11_sample() -> Result<(), Failed> {
+    let Ok(monkeys) = day11::parse(DAY11_SAMPLE.as_bytes()) else {
+        return Err(Failed);
+    };
+    check_eq!(day11::part1(&monkeys), Ok(10605));
+    check_eq!(day11::part2(&monkeys), Ok(2713310158));
+    check_eq!(day11::part2_cycles(&monkeys, 10_000), Ok(2713310158));
+    Ok(())
+}
+
+fn day11_rejects_missing_monkeys() -> Result<(), Failed> {
+    let first = DAY11_SAMPLE.split("\n\n").next().unwrap_or("");
+    check!(matches!(
+        day11::parse(first.as_bytes()),
+        Err(Error::Parse { day: 11, .. })
+    ));
+    Ok(())
+}
+
+fn day11_division_is_part1_only() -> Result<(), Failed> {
+    let input = "Monkey 0:\n  Starting items: 20\n  Operation: new = old / 2\n  Test: divisible by 5\n    If true: throw to monkey 1\n    If false: throw to monkey 1\n\nMonkey 1:\n  Starting items: 7\n  Operation: new = 100 - old\n  Test: divisible by 3\n    If true: throw to monkey 0\n    If false: throw to monkey 0\n";
+    let Ok(monkeys) = day11::parse(input.as_bytes()) else {
+        return Err(Failed);
+    };
+    check!(day11::part1(&monkeys).is_ok());
+    check!(matches!(
+        day11::part2(&monkeys),
+        Err(Error::Unsupported {
+            day: 11,
+            part: 2,
+            ..
+        })
+    ));
+    Ok(())
+}
+
+fn top_k_keeps_ties_in_order() -> Result<(), Failed> {
+    let mut top: TopK<3> = TopK::new();
+    for (index, value) in [5, 9, 5, 1, 9, 7].into_iter().enumerate() {
+        top.push(value, index);
+    }
+    check_eq!(top.entries(), &[(9, 1), (9, 4), (7, 5)][..]);
+    check_eq!(top.sum(), Some(25));
+    Ok(())
+}
+
+target_tests!(
+    day01_sample,
+    day01_sample_stats,
+    day02_sample,
+    day02_rock_paper_scissors_lizard_spock,
+    day04_sample,
+    malformed_lines_are_errors,
+    day06_samples,
+    day06_streams_any_length,
+    const_answers_match,
+    day11_sample,
+    day11_rejects_missing_monkeys,
+    day11_division_is_part1_only,
+    top_k_keeps_ties_in_order,
+);